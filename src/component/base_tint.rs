@@ -0,0 +1,10 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// The color a character is tinted while in some state, such as telegraphing an attack. Control systems set this
+/// instead of `Tint`, which is derived from it each frame so that effects like blinking can be layered on top.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct BaseTint(pub [f32; 3]);
+
+impl Component for BaseTint {
+	type Storage = DenseVecStorage<Self>;
+}
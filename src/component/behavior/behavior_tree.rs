@@ -0,0 +1,655 @@
+use crate::{
+	component::{
+		behavior::Wander,
+		Damage,
+		Direction,
+		Position,
+		ProjectileConfig,
+		TileCoords,
+		Velocity,
+	},
+	constants::TILE_SIZE,
+	resource::{CurrentRegion, GameRng},
+};
+
+use amethyst::ecs::{Component, DenseVecStorage, Entity};
+use rand::Rng;
+use serde::Deserialize;
+
+/// The result of ticking a behavior node.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Status {
+	/// The node needs more frames to finish.
+	Running,
+	Success,
+	Failure,
+}
+
+/// A kind of melee attack a behavior tree can start.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum MeleeAttack {
+	Slash(Damage),
+	Thrust(Damage),
+}
+
+/// A node of a behavior tree, as read from a prefab file. Leaf nodes act or check a condition, and composite nodes
+/// combine their children. A node that is running is ticked again on the next frame, until it succeeds or fails or
+/// a composite above it interrupts it.
+#[derive(PartialEq, Clone, Debug, Deserialize)]
+pub enum BehaviorNode {
+	/// Ticks its children in order. Fails as soon as one fails, and succeeds once all have succeeded. While a child
+	/// is running, the conditions before it are checked again each frame, and the child is interrupted if one fails.
+	Sequence {
+		children: Vec<BehaviorNode>,
+		#[serde(skip)]
+		current: usize,
+	},
+	/// Ticks its children in order. Succeeds as soon as one succeeds, and fails once all have failed. While a child
+	/// is running, the children before it are still ticked first each frame, and the running child is interrupted if
+	/// one of them doesn't fail.
+	Selector {
+		children: Vec<BehaviorNode>,
+		/// The index of the child left running on the last tick, if any.
+		#[serde(skip)]
+		running: Option<usize>,
+	},
+	/// Fails while cooling down. Otherwise ticks `child`, and starts cooling down for a random number of frames from
+	/// `min_frames` to `max_frames` each time it succeeds. The first cooldown starts right away, so that enemies
+	/// don't all act at once. Frames pass whether or not the node is ticked.
+	Cooldown {
+		min_frames: u32,
+		max_frames: u32,
+		child: Box<BehaviorNode>,
+		/// The last frame of the current cooldown, if it has started.
+		#[serde(skip)]
+		end_frame: Option<u64>,
+	},
+	/// Stands still for `frames` frames.
+	Wait {
+		frames: u32,
+		#[serde(skip)]
+		elapsed: u32,
+	},
+	/// Stands still for `frames` frames, tinted as a warning.
+	Telegraph {
+		frames: u32,
+		#[serde(skip)]
+		elapsed: u32,
+	},
+	/// Walks to the center of the `target` tile along the shortest path. Fails if there is no path.
+	MoveTo {
+		target: TileCoords,
+	},
+	/// Walks toward the nearest hero along the shortest path until within `distance` of them. Fails if there is no
+	/// living hero or no path to them.
+	ApproachHero {
+		distance: f32,
+	},
+	/// Takes a step toward the nearest hero along the shortest path and succeeds, if the hero is within `radius` and
+	/// in sight. Fails otherwise, or if there is no path.
+	Chase {
+		radius: f32,
+	},
+	/// Takes a step along the loop of tiles given by the enemy's `Waypoints` and succeeds. Fails if it has none or
+	/// there is no path to the next one.
+	Patrol {
+		#[serde(skip)]
+		next_idx: usize,
+	},
+	/// Turns toward the nearest hero. Fails if there is no living hero.
+	FaceHero,
+	/// Fires a projectile in the direction faced.
+	/// `accuracy` - From 0 to 1. The projectile leaves from up to `1 - accuracy` tiles to either side.
+	Shoot {
+		accuracy: f32,
+		projectile: ProjectileConfig,
+	},
+	/// Slashes in the direction faced, standing still until the slash ends after `frames` frames.
+	Slash {
+		frames: u32,
+		damage: Damage,
+		#[serde(skip)]
+		elapsed: u32,
+	},
+	/// Thrusts in the direction faced, rushing forward at `speed` until the thrust hits something. The thrust ends
+	/// after `frames` frames.
+	Thrust {
+		frames: u32,
+		speed: f32,
+		damage: Damage,
+		#[serde(skip)]
+		elapsed: u32,
+	},
+	/// Wanders around randomly for `frames` frames.
+	Wander {
+		frames: u32,
+		#[serde(skip)]
+		elapsed: u32,
+		#[serde(skip)]
+		wander: Wander,
+	},
+	/// Moves away from the nearest hero, around walls, until it is at least `radius` away. Fails if cornered.
+	Flee {
+		radius: f32,
+	},
+	/// Succeeds if a hero is in the same row or column and in sight, and fails otherwise.
+	HeroInLine,
+	/// Succeeds if a hero is within `radius`, and fails otherwise.
+	HeroWithin {
+		radius: f32,
+	},
+	/// Succeeds if the nearest hero is in sight, and fails otherwise.
+	HeroInSight,
+}
+
+impl BehaviorNode {
+	/// Checks that this node and its descendants are usable, so that mistakes in prefab files are reported when
+	/// they're loaded.
+	pub fn validate(&self) -> amethyst::Result<()> {
+		match self {
+			BehaviorNode::Sequence { children, .. } | BehaviorNode::Selector { children, .. } => {
+				children.iter().try_for_each(BehaviorNode::validate)
+			},
+			BehaviorNode::Cooldown { min_frames, max_frames, child, .. } => {
+				if min_frames > max_frames {
+					return Err(amethyst::Error::from_string(format!(
+						"Cooldown min_frames ({}) is greater than max_frames ({})",
+						min_frames,
+						max_frames,
+					)));
+				}
+				if *max_frames == u32::MAX {
+					return Err(amethyst::Error::from_string(format!(
+						"Cooldown max_frames must be less than {}",
+						u32::MAX,
+					)));
+				}
+				child.validate()
+			},
+			_ => Ok(()),
+		}
+	}
+
+	/// Whether the node only checks a condition, without acting.
+	fn is_condition(&self) -> bool {
+		match self {
+			BehaviorNode::HeroInLine | BehaviorNode::HeroWithin { .. } | BehaviorNode::HeroInSight => true,
+			_ => false,
+		}
+	}
+
+	/// Resets the node and its descendants to start over on their next tick, ending any melee attack they started.
+	fn abort(&mut self, context: &mut BehaviorContext) {
+		match self {
+			BehaviorNode::Sequence { children, current } => {
+				if let Some(child) = children.get_mut(*current) {
+					child.abort(context);
+				}
+				*current = 0;
+			},
+			BehaviorNode::Selector { children, running } => {
+				if let Some(running_idx) = running.take() {
+					children[running_idx].abort(context);
+				}
+			},
+			BehaviorNode::Cooldown { child, .. } => child.abort(context),
+			BehaviorNode::Wait { elapsed, .. }
+			| BehaviorNode::Telegraph { elapsed, .. }
+			| BehaviorNode::Wander { elapsed, .. } => *elapsed = 0,
+			BehaviorNode::Slash { elapsed, .. } | BehaviorNode::Thrust { elapsed, .. } => {
+				if *elapsed > 0 {
+					context.melee_attack_finished = true;
+				}
+				*elapsed = 0;
+			},
+			_ => {},
+		}
+	}
+
+	fn tick(&mut self, context: &mut BehaviorContext) -> Status {
+		match self {
+			BehaviorNode::Sequence { children, current } => {
+				// Check the conditions leading up to the running child again, in case they no longer hold.
+				for idx in 0..*current {
+					if children[idx].is_condition() && children[idx].tick(context) == Status::Failure {
+						children[*current].abort(context);
+						*current = 0;
+						return Status::Failure;
+					}
+				}
+				while *current < children.len() {
+					match children[*current].tick(context) {
+						Status::Running => return Status::Running,
+						Status::Success => *current += 1,
+						Status::Failure => {
+							*current = 0;
+							return Status::Failure;
+						},
+					}
+				}
+				*current = 0;
+				Status::Success
+			},
+			BehaviorNode::Selector { children, running } => {
+				// Children before the running one take priority, so they're ticked first again.
+				for idx in 0..children.len() {
+					let status = children[idx].tick(context);
+					if status == Status::Failure {
+						if *running == Some(idx) {
+							*running = None;
+						}
+						continue;
+					}
+					if let Some(running_idx) = running.take() {
+						if running_idx != idx {
+							children[running_idx].abort(context);
+						}
+					}
+					if status == Status::Running {
+						*running = Some(idx);
+					}
+					return status;
+				}
+				Status::Failure
+			},
+			BehaviorNode::Cooldown { min_frames, max_frames, child, end_frame } => {
+				let status = match *end_frame {
+					Some(end_frame) if context.frame <= end_frame => return Status::Failure,
+					Some(_) => child.tick(context),
+					None => Status::Failure,
+				};
+				if status == Status::Success || end_frame.is_none() {
+					let frames = context.rng.gen_range(*min_frames, *max_frames + 1);
+					*end_frame = Some(context.frame + u64::from(frames));
+				}
+				status
+			},
+			BehaviorNode::Wait { frames, elapsed } => count_frames(*frames, elapsed),
+			BehaviorNode::Telegraph { frames, elapsed } => {
+				let status = count_frames(*frames, elapsed);
+				context.telegraphing = status == Status::Running;
+				status
+			},
+			BehaviorNode::MoveTo { target } => {
+				let target: Position = (*target).into();
+				if context.position.distance_to(&target) <= context.speed {
+					return Status::Success;
+				}
+				let step_target = context.current_region.next_step(&context.position, &target);
+				if head_for(step_target, context) {
+					Status::Running
+				} else {
+					Status::Failure
+				}
+			},
+			BehaviorNode::ApproachHero { distance } => match context.hero_position {
+				Some(hero_position) if context.position.distance_to(&hero_position) <= *distance => Status::Success,
+				Some(hero_position) => {
+					let step_target = context.current_region.next_step(&context.position, &hero_position);
+					if head_for(step_target, context) {
+						Status::Running
+					} else {
+						Status::Failure
+					}
+				},
+				None => Status::Failure,
+			},
+			BehaviorNode::Chase { radius } => match context.hero_position {
+				Some(hero_position) if context.hero_in_sight && context.position.distance_to(&hero_position) <= *radius => {
+					let step_target = context.current_region.next_step(&context.position, &hero_position);
+					if head_for(step_target, context) {
+						Status::Success
+					} else {
+						Status::Failure
+					}
+				},
+				_ => Status::Failure,
+			},
+			BehaviorNode::Patrol { next_idx } => {
+				let waypoint_count = context.waypoints.len();
+				if waypoint_count == 0 {
+					return Status::Failure;
+				}
+				// Move on to the next waypoint once the current one is within a step.
+				*next_idx %= waypoint_count;
+				let mut target: Position = context.waypoints[*next_idx].into();
+				if context.position.distance_to(&target) <= context.speed {
+					*next_idx = (*next_idx + 1) % waypoint_count;
+					target = context.waypoints[*next_idx].into();
+				}
+				let step_target = context.current_region.next_step(&context.position, &target);
+				if head_for(step_target, context) {
+					Status::Success
+				} else {
+					Status::Failure
+				}
+			},
+			BehaviorNode::FaceHero => match context.hero_position {
+				Some(hero_position) => {
+					context.direction = Direction::nearest(
+						hero_position.x - context.position.x,
+						hero_position.y - context.position.y,
+					);
+					Status::Success
+				},
+				None => Status::Failure,
+			},
+			BehaviorNode::Shoot { accuracy, projectile } => {
+				let shot_position = shot_position(&mut *context.rng, context.position, context.direction, *accuracy);
+				context.shot = Some((shot_position, context.direction, *projectile));
+				Status::Success
+			},
+			BehaviorNode::Slash { frames, damage, elapsed } => {
+				melee_attack(MeleeAttack::Slash(*damage), *frames, elapsed, context)
+			},
+			BehaviorNode::Thrust { frames, speed, damage, elapsed } => {
+				// The thrust attack doesn't exist yet on the frame it starts.
+				let starting = *elapsed == 0;
+				let status = melee_attack(MeleeAttack::Thrust(*damage), *frames, elapsed, context);
+				if status == Status::Running && (starting || context.thrust_active) {
+					context.velocity = Velocity::in_direction(context.direction, *speed);
+				}
+				status
+			},
+			BehaviorNode::Wander { frames, elapsed, wander } => {
+				let status = count_frames(*frames, elapsed);
+				if status == Status::Running {
+					wander.update(&mut *context.rng);
+					if let Some(wander_direction) = wander.direction {
+						context.direction = wander_direction;
+						context.velocity = Velocity::in_direction(wander_direction, context.speed);
+					}
+				}
+				status
+			},
+			BehaviorNode::Flee { radius } => match context.hero_position {
+				Some(hero_position) if context.position.distance_to(&hero_position) < *radius => {
+					let step_target = context.current_region.retreat_step(&context.position, &hero_position);
+					if head_for(step_target, context) {
+						Status::Running
+					} else {
+						// Cornered.
+						Status::Failure
+					}
+				},
+				_ => Status::Success,
+			},
+			BehaviorNode::HeroInLine => if context.hero_in_line {
+				Status::Success
+			} else {
+				Status::Failure
+			},
+			BehaviorNode::HeroWithin { radius } => match context.hero_position {
+				Some(hero_position) if context.position.distance_to(&hero_position) <= *radius => Status::Success,
+				_ => Status::Failure,
+			},
+			BehaviorNode::HeroInSight => if context.hero_in_sight {
+				Status::Success
+			} else {
+				Status::Failure
+			},
+		}
+	}
+}
+
+/// Sets the velocity and direction to head for `step_target` at the enemy's speed, unless the enemy is already there.
+/// Returns whether the enemy moves.
+fn head_for(step_target: Position, context: &mut BehaviorContext) -> bool {
+	if step_target == context.position {
+		return false;
+	}
+	context.velocity = Velocity::toward(&context.position, &step_target, context.speed);
+	context.direction = Direction::nearest(context.velocity.x, context.velocity.y);
+	true
+}
+
+/// Where a projectile shot from `position` in `direction` starts: shifted sideways by a random amount of up to
+/// `1 - accuracy` tiles.
+fn shot_position(rng: &mut GameRng, position: Position, direction: Direction, accuracy: f32) -> Position {
+	let spread = (1.0 - accuracy) * TILE_SIZE;
+	let offset = if spread > 0.0 { rng.gen_range(-spread, spread) } else { 0.0 };
+	match direction {
+		Direction::Up | Direction::Down => Position { x: position.x + offset, y: position.y },
+		Direction::Left | Direction::Right => Position { x: position.x, y: position.y + offset },
+	}
+}
+
+/// Runs for `frames` frames, then succeeds and starts over.
+fn count_frames(frames: u32, elapsed: &mut u32) -> Status {
+	if *elapsed < frames {
+		*elapsed += 1;
+		Status::Running
+	} else {
+		*elapsed = 0;
+		Status::Success
+	}
+}
+
+/// Runs a melee attack of the given kind for `frames` frames, then succeeds and starts over.
+fn melee_attack(kind: MeleeAttack, frames: u32, elapsed: &mut u32, context: &mut BehaviorContext) -> Status {
+	if *elapsed == 0 && frames > 0 {
+		context.melee_attack = Some(kind);
+	}
+	let status = count_frames(frames, elapsed);
+	if status == Status::Success {
+		context.melee_attack_finished = true;
+	}
+	status
+}
+
+/// What a behavior tree can see and do on the current frame.
+pub struct BehaviorContext<'a> {
+	pub rng: &'a mut GameRng,
+	pub current_region: &'a mut CurrentRegion,
+	/// The number of frames enemies have been controlled for, counting this one.
+	pub frame: u64,
+	/// The enemy's movement speed.
+	pub speed: f32,
+	pub position: Position,
+	/// The position of the nearest living hero, if any.
+	pub hero_position: Option<Position>,
+	/// Whether the nearest living hero is in sight.
+	pub hero_in_sight: bool,
+	/// Whether a hero is in the enemy's row or column and in sight.
+	pub hero_in_line: bool,
+	/// The tiles the enemy patrols, if any.
+	pub waypoints: &'a [TileCoords],
+	/// The direction faced, which the tree may change.
+	pub direction: Direction,
+	/// Zero unless the tree moves the enemy this frame.
+	pub velocity: Velocity,
+	/// The position, direction and tuning of a projectile to fire this frame, if any.
+	pub shot: Option<(Position, Direction, ProjectileConfig)>,
+	/// Whether the enemy is telegraphing an attack this frame.
+	pub telegraphing: bool,
+	/// A melee attack to start this frame, if any.
+	pub melee_attack: Option<MeleeAttack>,
+	/// Whether the enemy's current melee attack ended this frame.
+	pub melee_attack_finished: bool,
+	/// Whether the enemy's current melee attack is a thrust that hasn't hit anything yet.
+	pub thrust_active: bool,
+}
+
+/// Behavior that controls an enemy according to a tree of nodes read from its prefab. The tree starts over from
+/// the root each time it succeeds or fails.
+#[derive(PartialEq, Clone, Debug)]
+pub struct BehaviorTree {
+	root: BehaviorNode,
+	telegraphing: bool,
+	/// The entity ID of the enemy's current melee attack, if any.
+	attack_id: Option<Entity>,
+}
+
+impl Component for BehaviorTree {
+	type Storage = DenseVecStorage<Self>;
+}
+
+impl BehaviorTree {
+	pub fn new(root: BehaviorNode) -> Self {
+		Self {
+			root,
+			telegraphing: false,
+			attack_id: None,
+		}
+	}
+
+	/// Records the entity ID of a melee attack started by the tree.
+	pub fn set_attack_id(&mut self, attack_id: Entity) {
+		self.attack_id = Some(attack_id);
+	}
+
+	/// The entity ID of the tree's current melee attack, if any.
+	pub fn attack_id(&self) -> Option<Entity> {
+		self.attack_id
+	}
+
+	/// Takes the entity ID of the tree's current melee attack, if any.
+	pub fn take_attack_id(&mut self) -> Option<Entity> {
+		self.attack_id.take()
+	}
+
+	/// Whether the enemy was telegraphing an attack as of the last tick.
+	pub fn is_telegraphing(&self) -> bool {
+		self.telegraphing
+	}
+
+	/// Advances the tree by one frame, reading from and writing to `context`.
+	pub fn tick(&mut self, context: &mut BehaviorContext) {
+		self.root.tick(context);
+		self.telegraphing = context.telegraphing;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Ticks `node` once with a hero at `hero_position` and an enemy at the origin.
+	fn tick(node: &mut BehaviorNode, hero_position: Option<Position>) -> Status {
+		tick_on_frame(node, 1, hero_position)
+	}
+
+	/// Like `tick`, but on the given frame.
+	fn tick_on_frame(node: &mut BehaviorNode, frame: u64, hero_position: Option<Position>) -> Status {
+		let mut rng = GameRng::new(0);
+		let mut current_region = CurrentRegion::new();
+		let mut context = BehaviorContext {
+			rng: &mut rng,
+			current_region: &mut current_region,
+			frame,
+			speed: 1.0,
+			position: Position { x: 0.0, y: 0.0 },
+			hero_position,
+			hero_in_sight: true,
+			hero_in_line: false,
+			waypoints: &[],
+			direction: Direction::Down,
+			velocity: Velocity::default(),
+			shot: None,
+			telegraphing: false,
+			melee_attack: None,
+			melee_attack_finished: false,
+			thrust_active: false,
+		};
+		node.tick(&mut context)
+	}
+
+	const NEAR: Option<Position> = Some(Position { x: 10.0, y: 0.0 });
+	const FAR: Option<Position> = Some(Position { x: 1000.0, y: 0.0 });
+
+	fn hero_within() -> BehaviorNode {
+		BehaviorNode::HeroWithin { radius: 100.0 }
+	}
+
+	fn wait(frames: u32) -> BehaviorNode {
+		BehaviorNode::Wait { frames, elapsed: 0 }
+	}
+
+	#[test]
+	fn sequence_interrupts_running_child_once_earlier_condition_fails() {
+		let mut sequence = BehaviorNode::Sequence { children: vec!(hero_within(), wait(2)), current: 0 };
+		assert_eq!(tick(&mut sequence, NEAR), Status::Running);
+		// The hero has left, so the wait is cut short.
+		assert_eq!(tick(&mut sequence, FAR), Status::Failure);
+		// Starting over, the wait runs in full.
+		assert_eq!(tick(&mut sequence, NEAR), Status::Running);
+		assert_eq!(tick(&mut sequence, NEAR), Status::Running);
+		assert_eq!(tick(&mut sequence, NEAR), Status::Success);
+	}
+
+	#[test]
+	fn sequence_starts_over_after_failing() {
+		let mut sequence = BehaviorNode::Sequence { children: vec!(wait(1), hero_within()), current: 0 };
+		assert_eq!(tick(&mut sequence, FAR), Status::Running);
+		assert_eq!(tick(&mut sequence, FAR), Status::Failure);
+		// The wait runs again instead of the failed check.
+		assert_eq!(tick(&mut sequence, NEAR), Status::Running);
+		assert_eq!(tick(&mut sequence, NEAR), Status::Success);
+	}
+
+	#[test]
+	fn selector_interrupts_running_child_once_earlier_child_succeeds() {
+		let mut selector = BehaviorNode::Selector { children: vec!(hero_within(), wait(2)), running: None };
+		assert_eq!(tick(&mut selector, FAR), Status::Running);
+		// The hero has arrived, so the wait is cut short.
+		assert_eq!(tick(&mut selector, NEAR), Status::Success);
+		// Once the hero leaves again, the wait runs in full.
+		assert_eq!(tick(&mut selector, FAR), Status::Running);
+		assert_eq!(tick(&mut selector, FAR), Status::Running);
+		assert_eq!(tick(&mut selector, FAR), Status::Success);
+	}
+
+	#[test]
+	fn selector_fails_once_all_children_fail_and_starts_over() {
+		let mut selector = BehaviorNode::Selector {
+			children: vec!(hero_within(), BehaviorNode::FaceHero),
+			running: None,
+		};
+		assert_eq!(tick(&mut selector, None), Status::Failure);
+		assert_eq!(tick(&mut selector, NEAR), Status::Success);
+	}
+
+	fn cooldown(frames: u32, child: BehaviorNode) -> BehaviorNode {
+		BehaviorNode::Cooldown { min_frames: frames, max_frames: frames, child: Box::new(child), end_frame: None }
+	}
+
+	#[test]
+	fn cooldown_fails_until_cooled_down() {
+		let mut cooldown = cooldown(2, hero_within());
+		for frame in 1..=3 {
+			assert_eq!(tick_on_frame(&mut cooldown, frame, NEAR), Status::Failure);
+		}
+		assert_eq!(tick_on_frame(&mut cooldown, 4, NEAR), Status::Success);
+		assert_eq!(tick_on_frame(&mut cooldown, 5, NEAR), Status::Failure);
+	}
+
+	#[test]
+	fn cooldown_counts_frames_spent_in_other_children() {
+		let wander = BehaviorNode::Wander { frames: 10, elapsed: 0, wander: Wander::default() };
+		let mut selector = BehaviorNode::Selector { children: vec!(cooldown(2, hero_within()), wander), running: None };
+		// The cooldown starts, and the enemy wanders while it runs out.
+		for frame in 1..=3 {
+			assert_eq!(tick_on_frame(&mut selector, frame, NEAR), Status::Running);
+		}
+		// Once it has, its child cuts the wander short.
+		assert_eq!(tick_on_frame(&mut selector, 4, NEAR), Status::Success);
+	}
+
+	#[test]
+	fn validate_rejects_backwards_cooldown_range() {
+		let cooldown = BehaviorNode::Cooldown {
+			min_frames: 3,
+			max_frames: 2,
+			child: Box::new(hero_within()),
+			end_frame: None,
+		};
+		let selector = BehaviorNode::Selector { children: vec!(cooldown), running: None };
+		assert!(selector.validate().is_err());
+	}
+
+	#[test]
+	fn validate_rejects_unbounded_cooldown() {
+		assert!(cooldown(u32::MAX, hero_within()).validate().is_err());
+		assert!(cooldown(u32::MAX - 1, hero_within()).validate().is_ok());
+	}
+}
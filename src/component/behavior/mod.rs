@@ -1,5 +1,7 @@
-mod shoot_arrows;
+mod behavior_tree;
 mod wander;
+mod waypoints;
 
-pub use shoot_arrows::{ShootArrows, ShouldShootArrow};
+pub use behavior_tree::{BehaviorContext, BehaviorNode, BehaviorTree, MeleeAttack, Status};
 pub use wander::Wander;
+pub use waypoints::Waypoints;
@@ -1,13 +1,23 @@
 use crate::component::Direction;
 
-use amethyst::ecs::{Component, DenseVecStorage};
+use rand::Rng;
 
-/// Behavior that causes an enemy to wander around randomly.
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+/// The state of a `Wander` behavior node: the direction an enemy is wandering in, if it isn't standing still.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Default)]
 pub struct Wander {
 	pub direction: Option<Direction>,
 }
 
-impl Component for Wander {
-	type Storage = DenseVecStorage<Self>;
+impl Wander {
+	/// Randomly changes the wander direction or stops.
+	pub fn update<R: Rng>(&mut self, rng: &mut R) {
+		const TURN_THRESHOLD: f32 = 0.01;
+		const STOP_THRESHOLD: f32 = TURN_THRESHOLD + 0.01;
+		let p = rng.gen::<f32>();
+		if (0.0..TURN_THRESHOLD).contains(&p) {
+			self.direction = Some(rng.gen());
+		} else if (TURN_THRESHOLD..STOP_THRESHOLD).contains(&p) {
+			self.direction = None;
+		}
+	}
 }
@@ -0,0 +1,11 @@
+use crate::component::TileCoords;
+
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// The tiles an enemy visits in a loop when its behavior tree patrols.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Waypoints(pub Vec<TileCoords>);
+
+impl Component for Waypoints {
+	type Storage = DenseVecStorage<Self>;
+}
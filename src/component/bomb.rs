@@ -0,0 +1,50 @@
+use crate::{
+	component::{Damage, Faction},
+	constants::*,
+};
+
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// A placed bomb. Once its fuse runs out, it damages and knocks back nearby characters from other factions.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Bomb {
+	faction: Faction,
+	damage: Damage,
+	fuse_frames_left: u32,
+}
+
+impl Component for Bomb {
+	type Storage = DenseVecStorage<Self>;
+}
+
+impl Bomb {
+	/// `faction` The faction of the entity that placed this bomb.
+	/// `damage` The damage dealt by the explosion.
+	pub fn new(faction: Faction, damage: Damage) -> Self {
+		Self {
+			faction,
+			damage,
+			fuse_frames_left: BOMB_FUSE_FRAMES,
+		}
+	}
+
+	/// The faction of the entity that placed this bomb.
+	pub fn faction(&self) -> Faction {
+		self.faction
+	}
+
+	/// The damage dealt by the explosion, before resistances.
+	pub fn damage(&self) -> Damage {
+		self.damage
+	}
+
+	/// Whether the fuse has run out, meaning the bomb explodes this frame.
+	pub fn is_exploding(&self) -> bool {
+		self.fuse_frames_left == 0
+	}
+
+	/// Burns the fuse down by one frame.
+	pub fn update(&mut self) {
+		self.fuse_frames_left = self.fuse_frames_left.saturating_sub(1);
+	}
+}
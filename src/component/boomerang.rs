@@ -0,0 +1,87 @@
+use crate::{
+	component::{Damage, Faction, collider::RectangleCollider},
+	constants::*,
+};
+
+use amethyst::ecs::{Component, DenseVecStorage, Entity};
+
+use std::collections::HashSet;
+
+/// A thrown boomerang. It flies straight for a while, then returns to its thrower, hitting characters from other
+/// factions along the way.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Boomerang {
+	source_id: Entity,
+	faction: Faction,
+	damage: Damage,
+	outward_frames_left: u32,
+	/// IDs of characters this boomerang has already damaged.
+	already_hit_ids: HashSet<Entity>,
+}
+
+impl Component for Boomerang {
+	type Storage = DenseVecStorage<Self>;
+}
+
+impl Boomerang {
+	/// `source_id` The entity ID of the character that threw this boomerang.
+	/// `faction` The faction of the character that threw this boomerang.
+	/// `damage` The damage dealt to each character hit.
+	pub fn new(source_id: Entity, faction: Faction, damage: Damage) -> Self {
+		Self {
+			source_id,
+			faction,
+			damage,
+			outward_frames_left: BOOMERANG_OUTWARD_FRAMES,
+			already_hit_ids: HashSet::new(),
+		}
+	}
+
+	/// The entity ID of the character that threw this boomerang.
+	pub fn source_id(&self) -> Entity {
+		self.source_id
+	}
+
+	/// The faction of the character that threw this boomerang.
+	pub fn faction(&self) -> Faction {
+		self.faction
+	}
+
+	/// The damage dealt to each character hit, before resistances.
+	pub fn damage(&self) -> Damage {
+		self.damage
+	}
+
+	/// Whether the boomerang is flying back to its thrower.
+	pub fn is_returning(&self) -> bool {
+		self.outward_frames_left == 0
+	}
+
+	/// Turns the boomerang around, e.g. because it hit a wall.
+	pub fn start_returning(&mut self) {
+		self.outward_frames_left = 0;
+	}
+
+	/// Advances the outward flight by one frame.
+	pub fn update(&mut self) {
+		self.outward_frames_left = self.outward_frames_left.saturating_sub(1);
+	}
+
+	/// Checks whether the entity with the given ID has already been hit by this boomerang.
+	pub fn has_been_hit(&self, id: Entity) -> bool {
+		self.already_hit_ids.contains(&id)
+	}
+
+	/// Marks the entity with the given ID as having been hit by this boomerang.
+	pub fn mark_as_hit(&mut self, id: Entity) {
+		self.already_hit_ids.insert(id);
+	}
+
+	/// The collider of a boomerang, which is the same in every direction.
+	pub fn collider() -> RectangleCollider {
+		RectangleCollider {
+			half_width: BOOMERANG_HALF_SIZE,
+			half_height: BOOMERANG_HALF_SIZE,
+		}
+	}
+}
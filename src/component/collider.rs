@@ -29,6 +29,16 @@ impl Component for HalfDiskCollider {
 	type Storage = DenseVecStorage<Self>;
 }
 
+/// Disk collider.
+#[derive(PartialEq, Copy, Clone, Debug, Deserialize)]
+pub struct DiskCollider {
+	pub radius: f32,
+}
+
+impl Component for DiskCollider {
+	type Storage = DenseVecStorage<Self>;
+}
+
 /// Determines the area of intersection between two rectangle colliders.
 pub fn rect_rect_intersection_area(first: (&RectangleCollider, &Position), second: (&RectangleCollider, &Position)) -> f32 {
 	let (collider1, pos1) = first;
@@ -66,3 +76,16 @@ pub fn rect_intersects_half_disk(rectangle: (&RectangleCollider, &Position), hal
 	let distance_squared = (closest.x - disk_pos.x).powi(2) + (closest.y - disk_pos.y).powi(2);
 	distance_squared - EPSILON <= half_disk_collider.radius.powi(2)
 }
+
+/// Determines whether `rectangle` and `disk` intersect.
+pub fn rect_intersects_disk(rectangle: (&RectangleCollider, &Position), disk: (&DiskCollider, &Position)) -> bool {
+	let (rect_collider, rect_pos) = rectangle;
+	let (disk_collider, disk_pos) = disk;
+	// Find the point inside the rectangle that is closest to the disk's center, and check whether it's in the disk.
+	let closest = Position {
+		x: clamp(disk_pos.x, rect_pos.x - rect_collider.half_width, rect_pos.x + rect_collider.half_width),
+		y: clamp(disk_pos.y, rect_pos.y - rect_collider.half_height, rect_pos.y + rect_collider.half_height),
+	};
+	let distance_squared = (closest.x - disk_pos.x).powi(2) + (closest.y - disk_pos.y).powi(2);
+	distance_squared <= disk_collider.radius.powi(2)
+}
@@ -0,0 +1,122 @@
+use crate::component::Damage;
+
+use amethyst::ecs::{Component, DenseVecStorage};
+use serde::Deserialize;
+
+/// The kind of attack made by one strike of a combo, along with the tuning that affects its hitbox or motion.
+#[derive(PartialEq, Copy, Clone, Debug, Deserialize)]
+pub enum StrikeAttack {
+	/// A sweep in front of the attacker, hitting a half disk of the given radius.
+	Slash {
+		radius: f32,
+	},
+	/// A lunge, rushing forward at `speed` until the thrust hits something.
+	Thrust {
+		speed: f32,
+	},
+}
+
+/// One sword attack in a combo chain.
+#[derive(PartialEq, Copy, Clone, Debug, Deserialize)]
+pub struct Strike {
+	pub attack: StrikeAttack,
+	pub damage: Damage,
+	/// The length of the strike, in frames.
+	pub frames: u32,
+	/// The number of frames at the end of the strike during which a primary action press cuts it short to chain
+	/// into the next strike.
+	pub cancel_frames: u32,
+}
+
+/// Which of a character's combo chains an attack starts.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum ComboChain {
+	Slash,
+	Thrust,
+}
+
+/// Used for reading a character's combo chains from a file.
+#[derive(PartialEq, Clone, Debug, Deserialize)]
+pub struct ComboConfig {
+	/// The number of frames a primary action press is remembered, so that pressing a little before a cancel window
+	/// still chains into the next strike.
+	pub buffer_frames: u32,
+	/// The strikes chained after attacking while standing still, strafing or retreating.
+	pub slash: Vec<Strike>,
+	/// The strikes chained after attacking while advancing.
+	pub thrust: Vec<Strike>,
+}
+
+/// A character's sword combos, and its progress through the chain it's currently performing.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Combo {
+	config: ComboConfig,
+	/// The chain being performed and the index of its current strike, if any.
+	current: Option<(ComboChain, usize)>,
+	/// The number of frames left before the last buffered press is forgotten, or zero if there is none.
+	buffered_frames_left: u32,
+}
+
+impl Component for Combo {
+	type Storage = DenseVecStorage<Self>;
+}
+
+impl Combo {
+	pub fn new(config: ComboConfig) -> Self {
+		Self {
+			config,
+			current: None,
+			buffered_frames_left: 0,
+		}
+	}
+
+	/// Remembers a primary action press for the configured number of frames.
+	pub fn buffer_press(&mut self) {
+		self.buffered_frames_left = self.config.buffer_frames + 1;
+	}
+
+	/// Consumes the buffered press, returning whether there was one.
+	pub fn take_buffered_press(&mut self) -> bool {
+		let buffered = self.buffered_frames_left > 0;
+		self.buffered_frames_left = 0;
+		buffered
+	}
+
+	/// Starts `chain` from its first strike, consuming any buffered press. Returns `None` if the chain is empty.
+	pub fn start(&mut self, chain: ComboChain) -> Option<Strike> {
+		self.buffered_frames_left = 0;
+		self.current = Some((chain, 0));
+		self.current_strike()
+	}
+
+	/// The strike currently being performed, if any.
+	pub fn current_strike(&self) -> Option<Strike> {
+		self.current.and_then(|(chain, idx)| self.strikes(chain).get(idx).copied())
+	}
+
+	/// Moves on to the next strike of the current chain if a press is buffered, the current strike has no more than
+	/// its cancel window of `frames_left` frames left, and the chain has another strike. Returns the next strike if
+	/// so.
+	pub fn next(&mut self, frames_left: u32) -> Option<Strike> {
+		let (chain, idx) = self.current?;
+		let in_cancel_window = frames_left <= self.strikes(chain)[idx].cancel_frames;
+		if self.buffered_frames_left == 0 || !in_cancel_window || idx + 1 >= self.strikes(chain).len() {
+			return None;
+		}
+		self.buffered_frames_left = 0;
+		self.current = Some((chain, idx + 1));
+		self.current_strike()
+	}
+
+	/// Ages the buffered press by one frame. To be called once per frame.
+	pub fn update(&mut self) {
+		self.buffered_frames_left = self.buffered_frames_left.saturating_sub(1);
+	}
+
+	fn strikes(&self, chain: ComboChain) -> &Vec<Strike> {
+		match chain {
+			ComboChain::Slash => &self.config.slash,
+			ComboChain::Thrust => &self.config.thrust,
+		}
+	}
+}
@@ -0,0 +1,49 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+use serde::Deserialize;
+
+use std::collections::HashMap;
+
+/// A kind of damage, which characters may resist or be weak against.
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug, Deserialize)]
+pub enum DamageType {
+	Physical,
+	Pierce,
+	Fire,
+}
+
+/// The damage dealt by a hit, before the target's resistances are applied.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Deserialize)]
+pub struct Damage {
+	pub amount: u32,
+	pub damage_type: DamageType,
+}
+
+/// The damage dealt by each of the hero's weapons, besides the sword strikes given by their combos.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Deserialize)]
+pub struct WeaponDamage {
+	pub spin: Damage,
+	pub arrow: Damage,
+	pub bomb: Damage,
+	pub boomerang: Damage,
+}
+
+impl Component for WeaponDamage {
+	type Storage = DenseVecStorage<Self>;
+}
+
+/// Multipliers applied to the damage a character takes, by damage type. Types without a multiplier deal full
+/// damage. Values below 1 are resistances, values above 1 are weaknesses, and 0 is immunity.
+#[derive(PartialEq, Clone, Debug, Deserialize)]
+pub struct Resistances(pub HashMap<DamageType, f32>);
+
+impl Component for Resistances {
+	type Storage = DenseVecStorage<Self>;
+}
+
+impl Resistances {
+	/// The amount of `damage` a character with these resistances takes, rounded to the nearest whole amount.
+	pub fn apply(&self, damage: Damage) -> u32 {
+		let multiplier = self.0.get(&damage.damage_type).copied().unwrap_or(1.0);
+		(damage.amount as f32 * multiplier).round().max(0.0) as u32
+	}
+}
@@ -1,11 +1,11 @@
 use amethyst::ecs::{Component, DenseVecStorage};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use rand::{
 	distributions::{Distribution, Standard},
 	Rng,
 };
 
-#[derive(Eq, PartialEq, Copy, Clone, Debug, Deserialize)]
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
 #[repr(usize)]
 pub enum Direction {
 	Up,
@@ -23,6 +23,26 @@ impl Direction {
 			Direction::Right => Direction::Left,
 		}
 	}
+
+	/// The direction closest to the vector (`x`, `y`). Ties go to the vertical direction.
+	pub fn nearest(x: f32, y: f32) -> Self {
+		match (x.abs() > y.abs(), x < 0.0, y < 0.0) {
+			(true, true, _) => Direction::Left,
+			(true, false, _) => Direction::Right,
+			(false, _, true) => Direction::Down,
+			(false, _, false) => Direction::Up,
+		}
+	}
+
+	/// The direction a quarter turn clockwise from this one.
+	pub fn clockwise(&self) -> Self {
+		match self {
+			Direction::Up => Direction::Right,
+			Direction::Right => Direction::Down,
+			Direction::Down => Direction::Left,
+			Direction::Left => Direction::Up,
+		}
+	}
 }
 
 impl Component for Direction {
@@ -1,7 +1,14 @@
+use crate::component::Damage;
+
 use amethyst::ecs::{Component, DenseVecStorage};
 
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
-pub struct Enemy;
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct Enemy {
+	/// Movement speed while acting on behaviors.
+	pub speed: f32,
+	/// The damage dealt to a hero by running into them.
+	pub contact_damage: Damage,
+}
 
 impl Component for Enemy {
 	type Storage = DenseVecStorage<Self>;
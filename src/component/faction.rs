@@ -1,8 +1,9 @@
 use amethyst::ecs::{Component, DenseVecStorage};
+use serde::Deserialize;
 
 /// A character's relationship to the hero. All characters have a faction, so the
 /// presence of this component can be used to check if an entity is a character.
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Deserialize)]
 pub enum Faction {
 	Ally,
 	Enemy,
@@ -1,7 +1,8 @@
 use amethyst::ecs::{Component, DenseVecStorage};
+use serde::{Deserialize, Serialize};
 
 /// Represents a character's health or hit points.
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
 pub struct Health {
 	current: i32,
 	max: i32,
@@ -2,7 +2,10 @@ use amethyst::ecs::{Component, DenseVecStorage};
 
 /// A health pickup.
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
-pub struct Heart;
+pub struct Heart {
+	/// The amount of health restored by picking up this heart.
+	pub heal: u32,
+}
 
 impl Component for Heart {
 	type Storage = DenseVecStorage<Self>;
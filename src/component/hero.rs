@@ -3,6 +3,8 @@ use amethyst::ecs::{Component, DenseVecStorage, Entity};
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
 pub struct Hero {
 	pub state: HeroState,
+	/// The number of frames until the hero can roll again.
+	pub roll_cooldown_frames_left: u32,
 }
 
 impl Component for Hero {
@@ -20,4 +22,21 @@ pub enum HeroState {
 		thrust_attack_id: Entity,
 		frames_left: u32,
 	},
+	/// Dodging in a burst of speed, immune to damage.
+	Rolling {
+		frames_left: u32,
+	},
+	/// Holding the sword back after holding the primary action long enough, ready to spin when it's released.
+	Charging,
+	/// Spinning in place with the sword out after releasing a charge.
+	Spinning {
+		spin_attack_id: Entity,
+		frames_left: u32,
+	},
+	/// Out of health and playing the death animation.
+	Dying {
+		frames_left: u32,
+	},
+	/// Finished dying. The game is over until the hero respawns.
+	Dead,
 }
@@ -0,0 +1,18 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+use serde::Deserialize;
+
+/// How a character reacts to being hit. Characters without this component are neither knocked back nor made
+/// invulnerable by hits.
+#[derive(PartialEq, Copy, Clone, Debug, Deserialize)]
+pub struct HitReaction {
+	/// The speed at which the character is knocked away from a hit.
+	pub knockback_speed: f32,
+	/// The number of frames knockback lasts.
+	pub knockback_frames: u32,
+	/// The number of frames the character is immune to damage after being damaged. Zero for none.
+	pub invulnerable_frames: u32,
+}
+
+impl Component for HitReaction {
+	type Storage = DenseVecStorage<Self>;
+}
@@ -1,17 +1,13 @@
 use crate::constants::*;
 
-use amethyst::{
-	ecs::{Component, DenseVecStorage, Entity, WriteStorage},
-	renderer::{
-		palette::Srgba,
-		resources::Tint,
-	},
-};
+use amethyst::ecs::{Component, DenseVecStorage};
 
 /// Makes a character immune to damage for a number of frames.
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
 pub struct Invulnerable {
 	frames_left: u32,
+	/// Whether the character blinks while invulnerable.
+	blinking: bool,
 }
 
 impl Component for Invulnerable {
@@ -19,30 +15,48 @@ impl Component for Invulnerable {
 }
 
 impl Invulnerable {
-	pub fn new() -> Self {
+	/// `frames` - The number of frames the invulnerability lasts. Must be positive.
+	pub fn new(frames: u32) -> Self {
 		Self {
-			frames_left: INVULNERABLE_FRAMES,
+			frames_left: frames,
+			blinking: true,
 		}
 	}
 
+	/// Invulnerability that doesn't make the character blink, such as while dodging.
+	/// `frames` - The number of frames the invulnerability lasts. Must be positive.
+	pub fn without_blinking(frames: u32) -> Self {
+		Self {
+			frames_left: frames,
+			blinking: false,
+		}
+	}
+
+	/// The number of frames until the invulnerability wears off.
+	pub fn frames_left(&self) -> u32 {
+		self.frames_left
+	}
+
+	/// Whether the character blinks while invulnerable.
+	pub fn is_blinking(&self) -> bool {
+		self.blinking
+	}
+
 	/// Updates time left and returns whether the invulnerable effect has worn off.
-	/// `id` - The ID of the entity with invulnerability.
-	/// `sto_tint` - Tint component storage, used to update the blinking effect.
 	#[must_use]
-	pub fn update<'a>(&mut self, id: Entity, sto_tint: &mut WriteStorage<'a, Tint>) -> InvulnerableFinished {
+	pub fn update(&mut self) -> InvulnerableFinished {
 		self.frames_left -= 1;
-		if (self.frames_left / INVULNERABLE_BLINK_PERIOD) % 2 == 0 {
-			sto_tint.remove(id);
-		} else {
-			let lum = INVULNERABLE_BLINK_LUMINANCE;
-			sto_tint.insert(id, Tint(Srgba::new(lum, lum, lum, 1.0))).unwrap();
-		}
 		if self.frames_left == 0 {
 			InvulnerableFinished::Yes
 		} else {
 			InvulnerableFinished::No
 		}
 	}
+
+	/// Whether the character should currently be drawn dimmed, as part of the blinking effect.
+	pub fn is_dimmed(&self) -> bool {
+		(self.frames_left / INVULNERABLE_BLINK_PERIOD) % 2 != 0
+	}
 }
 
 pub enum InvulnerableFinished {
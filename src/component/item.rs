@@ -0,0 +1,77 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+use serde::{Deserialize, Serialize};
+
+/// An item the hero can use with the secondary action.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum Item {
+	/// Fires an arrow in the hero's direction.
+	Bow,
+	/// Places a bomb that explodes after a short fuse.
+	Bomb,
+	/// Throws a boomerang that flies out and returns to the hero.
+	Boomerang,
+}
+
+impl Default for Item {
+	fn default() -> Self {
+		Item::Bow
+	}
+}
+
+impl Item {
+	/// The item after this one in the selection order, wrapping around.
+	pub fn next(self) -> Self {
+		match self {
+			Item::Bow => Item::Bomb,
+			Item::Bomb => Item::Boomerang,
+			Item::Boomerang => Item::Bow,
+		}
+	}
+
+	/// The index of this item's sprite in the items sprite sheet.
+	pub fn sprite_number(self) -> usize {
+		match self {
+			Item::Bow => 0,
+			Item::Bomb => 1,
+			Item::Boomerang => 2,
+		}
+	}
+}
+
+/// The item a character has equipped, used with the secondary action.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct ItemSlot {
+	/// The currently selected item.
+	pub item: Item,
+	/// The number of frames until an item can be used again.
+	cooldown_frames_left: u32,
+}
+
+impl Component for ItemSlot {
+	type Storage = DenseVecStorage<Self>;
+}
+
+impl ItemSlot {
+	pub fn new(item: Item) -> Self {
+		Self {
+			item,
+			cooldown_frames_left: 0,
+		}
+	}
+
+	/// Whether the cooldown from the last item use has worn off.
+	pub fn is_ready(&self) -> bool {
+		self.cooldown_frames_left == 0
+	}
+
+	/// Starts the cooldown that must wear off before an item can be used again.
+	/// `frames` - The length of the cooldown, in frames.
+	pub fn start_cooldown(&mut self, frames: u32) {
+		self.cooldown_frames_left = frames;
+	}
+
+	/// Advances the cooldown by one frame.
+	pub fn update(&mut self) {
+		self.cooldown_frames_left = self.cooldown_frames_left.saturating_sub(1);
+	}
+}
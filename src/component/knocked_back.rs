@@ -1,7 +1,4 @@
-use crate::{
-	component::{Position, Velocity},
-	constants::*,
-};
+use crate::component::{HitReaction, Position, Velocity};
 
 use amethyst::ecs::{Component, DenseVecStorage};
 use serde::Deserialize;
@@ -18,8 +15,9 @@ impl Component for KnockedBack {
 }
 
 impl KnockedBack {
-	/// Creates a knockback in the direction from `from` to `to`, at standard speed (`constants::KNOCKBACK_SPEED`).
-	pub fn from_positions(from: &Position, to: &Position) -> Self {
+	/// Creates a knockback in the direction from `from` to `to`, with the speed and duration of `hit_reaction`.
+	pub fn from_positions(from: &Position, to: &Position, hit_reaction: &HitReaction) -> Self {
+		let speed = hit_reaction.knockback_speed;
 		let mut velocity = Velocity {
 			x: to.x - from.x,
 			y: to.y - from.y,
@@ -27,15 +25,15 @@ impl KnockedBack {
 		// Normalize knockback velocity to the desired speed.
 		if velocity.x != 0.0 || velocity.y != 0.0 {
 			let magnitude = f32::sqrt(velocity.x.powi(2) + velocity.y.powi(2));
-			velocity.x *= KNOCKBACK_SPEED / magnitude;
-			velocity.y *= KNOCKBACK_SPEED / magnitude;
+			velocity.x *= speed / magnitude;
+			velocity.y *= speed / magnitude;
 		} else {
 			// To and from are direction on top of each other. Knock towards the right arbitrarily.
-			velocity.x = KNOCKBACK_SPEED;
+			velocity.x = speed;
 		}
 		Self {
 			velocity,
-			frames_left: KNOCKBACK_FRAMES,
+			frames_left: hit_reaction.knockback_frames,
 		}
 	}
 
@@ -1,35 +1,53 @@
 pub mod animation;
-mod arrow_attack;
 pub mod behavior;
+mod base_tint;
+mod bomb;
+mod boomerang;
 pub mod collider;
+mod combo;
+mod damage;
 mod direction;
 mod enemy;
 mod faction;
 mod health;
 mod heart;
 mod hero;
+mod hit_reaction;
 mod invulnerable;
+mod item;
 mod knocked_back;
 mod position;
+mod projectile;
 pub mod removal;
 mod shield;
 mod slash_attack;
+mod spin_attack;
+mod spawn_index;
 mod terrain;
 mod thrust_attack;
 mod velocity;
 
-pub use arrow_attack::ArrowAttack;
+pub use base_tint::BaseTint;
+pub use bomb::Bomb;
+pub use boomerang::Boomerang;
+pub use combo::{Combo, ComboChain, ComboConfig, Strike, StrikeAttack};
+pub use damage::{Damage, DamageType, Resistances, WeaponDamage};
 pub use direction::Direction;
 pub use enemy::Enemy;
 pub use faction::Faction;
 pub use health::Health;
 pub use heart::Heart;
 pub use hero::{Hero, HeroState};
+pub use hit_reaction::HitReaction;
 pub use invulnerable::{Invulnerable, InvulnerableFinished};
+pub use item::{Item, ItemSlot};
 pub use knocked_back::{KnockedBack, KnockedBackFinished};
 pub use position::{Position, TileCoords};
-pub use shield::Shield;
-pub use slash_attack::SlashAttack;
+pub use projectile::{Projectile, ProjectileConfig, ProjectileStopped, ProjectileStorages};
+pub use shield::{Shield, ShieldConfig, ShieldReflect};
+pub use slash_attack::{SlashAttack, SlashAttackStorages};
+pub use spin_attack::{SpinAttack, SpinAttackStorages};
+pub use spawn_index::SpawnIndex;
 pub use terrain::Terrain;
-pub use thrust_attack::ThrustAttack;
+pub use thrust_attack::{ThrustAttack, ThrustAttackStorages};
 pub use velocity::Velocity;
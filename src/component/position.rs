@@ -1,9 +1,9 @@
 use crate::constants::TILE_SIZE;
 
 use amethyst::ecs::{Component, DenseVecStorage};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Copy, Clone, Debug, Deserialize)]
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
 pub struct Position {
 	pub x: f32,
 	pub y: f32,
@@ -13,7 +13,14 @@ impl Component for Position {
 	type Storage = DenseVecStorage<Self>;
 }
 
-#[derive(Eq, PartialEq, Copy, Clone, Debug, Deserialize)]
+impl Position {
+	/// The straight-line distance from this position to `other`.
+	pub fn distance_to(&self, other: &Position) -> f32 {
+		f32::sqrt((other.x - self.x).powi(2) + (other.y - self.y).powi(2))
+	}
+}
+
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug, Deserialize)]
 pub struct TileCoords {
 	pub row: usize,
 	pub col: usize,
@@ -0,0 +1,233 @@
+use crate::{
+	component::{
+		animation::{DirectionalAnimation, DirectionalFrame},
+		collider::RectangleCollider,
+		Damage,
+		Direction,
+		Faction,
+		Position,
+		removal::TiedToRegion,
+		Velocity,
+	},
+	constants::*,
+};
+
+use amethyst::{
+	assets::Handle,
+	ecs::{Component, DenseVecStorage, Entities, Entity, WriteStorage},
+	renderer::SpriteSheet,
+	utils::removal::Removal,
+};
+use serde::Deserialize;
+
+use std::collections::HashSet;
+
+/// The component storages a projectile entity is built into.
+pub type ProjectileStorages<'s, 'a> = (
+	&'s mut WriteStorage<'a, Projectile>,
+	&'s mut WriteStorage<'a, Removal<TiedToRegion>>,
+	&'s mut WriteStorage<'a, Position>,
+	&'s mut WriteStorage<'a, Velocity>,
+	&'s mut WriteStorage<'a, Direction>,
+	&'s mut WriteStorage<'a, RectangleCollider>,
+	&'s mut WriteStorage<'a, DirectionalAnimation>,
+);
+
+/// Used for reading a projectile's tuning from a file. Only `speed` and `damage` are required; by default a
+/// projectile flies straight until it hits a wall or a character.
+#[derive(PartialEq, Copy, Clone, Debug, Deserialize)]
+pub struct ProjectileConfig {
+	/// The distance the projectile moves each frame.
+	pub speed: f32,
+	/// The damage dealt to each character hit.
+	pub damage: Damage,
+	/// The number of frames the projectile flies before vanishing, which limits its range to `speed * lifetime`.
+	/// `None` means it flies until it hits something.
+	#[serde(default)]
+	pub lifetime: Option<u32>,
+	/// The number of characters the projectile passes through before stopping at the next one.
+	#[serde(default)]
+	pub pierce: u32,
+	/// The number of times the projectile bounces off walls before breaking on the next one.
+	#[serde(default)]
+	pub bounces: u32,
+	/// The angle in radians the projectile's flight turns each frame, counterclockwise if positive. A nonzero turn
+	/// rate bends the flight into an arc.
+	#[serde(default)]
+	pub turn_rate: f32,
+}
+
+impl ProjectileConfig {
+	/// An arrow, which flies straight at `speed` until it hits a wall or a character.
+	pub fn arrow(speed: f32, damage: Damage) -> Self {
+		Self {
+			speed,
+			damage,
+			lifetime: None,
+			pierce: 0,
+			bounces: 0,
+			turn_rate: 0.0,
+		}
+	}
+}
+
+/// Something fired through the air, such as an arrow or a fireball, that hits characters from other factions.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Projectile {
+	config: ProjectileConfig,
+	faction: Faction,
+	frames_left: Option<u32>,
+	pierces_left: u32,
+	bounces_left: u32,
+	/// IDs of characters this projectile has already hit.
+	already_hit_ids: HashSet<Entity>,
+}
+
+impl Component for Projectile {
+	type Storage = DenseVecStorage<Self>;
+}
+
+impl Projectile {
+	/// `config` The projectile's tuning.
+	/// `faction` The faction of the entity that fired this projectile.
+	pub fn new(config: ProjectileConfig, faction: Faction) -> Self {
+		Self {
+			config,
+			faction,
+			frames_left: config.lifetime,
+			pierces_left: config.pierce,
+			bounces_left: config.bounces,
+			already_hit_ids: HashSet::new(),
+		}
+	}
+
+	/// The faction of the entity that fired this projectile.
+	pub fn faction(&self) -> Faction {
+		self.faction
+	}
+
+	/// The damage dealt to each character hit, before resistances.
+	pub fn damage(&self) -> Damage {
+		self.config.damage
+	}
+
+	/// Checks whether the entity with the given ID has already been hit by this projectile.
+	pub fn has_been_hit(&self, id: Entity) -> bool {
+		self.already_hit_ids.contains(&id)
+	}
+
+	/// Marks the entity with the given ID as hit, using up a pierce if the projectile has any left.
+	pub fn hit(&mut self, id: Entity) -> ProjectileStopped {
+		self.already_hit_ids.insert(id);
+		if self.pierces_left > 0 {
+			self.pierces_left -= 1;
+			ProjectileStopped::No
+		} else {
+			ProjectileStopped::Yes
+		}
+	}
+
+	/// Uses up a bounce after hitting a wall, if the projectile has any left.
+	pub fn bounce(&mut self) -> ProjectileStopped {
+		if self.bounces_left > 0 {
+			self.bounces_left -= 1;
+			ProjectileStopped::No
+		} else {
+			ProjectileStopped::Yes
+		}
+	}
+
+	/// Sends the projectile back the way it came, as if freshly fired by a character from `faction`.
+	pub fn reflect(&mut self, faction: Faction, velocity: &mut Velocity, direction: &mut Direction) {
+		self.faction = faction;
+		self.frames_left = self.config.lifetime;
+		velocity.x = -velocity.x;
+		velocity.y = -velocity.y;
+		*direction = direction.opposite();
+	}
+
+	/// Advances the projectile's flight by one frame, turning `velocity` by the turn rate.
+	pub fn update(&mut self, velocity: &mut Velocity) -> ProjectileStopped {
+		if self.config.turn_rate != 0.0 {
+			let (sin, cos) = self.config.turn_rate.sin_cos();
+			*velocity = Velocity {
+				x: velocity.x * cos - velocity.y * sin,
+				y: velocity.x * sin + velocity.y * cos,
+			};
+		}
+		match self.frames_left {
+			Some(ref mut frames_left) => {
+				*frames_left = frames_left.saturating_sub(1);
+				if *frames_left == 0 {
+					ProjectileStopped::Yes
+				} else {
+					ProjectileStopped::No
+				}
+			},
+			None => ProjectileStopped::No,
+		}
+	}
+
+	/// Fires a projectile from `position` in `direction` and returns its entity ID.
+	/// `sprite_sheet` - The projectile's sprite sheet, with one sprite per direction.
+	/// `config` - The projectile's tuning.
+	/// `faction` - The faction of the entity firing the projectile.
+	pub fn spawn(
+		entities: &Entities,
+		sprite_sheet: Handle<SpriteSheet>,
+		config: ProjectileConfig,
+		faction: Faction,
+		position: Position,
+		direction: Direction,
+		(
+			sto_projectile,
+			sto_removal_tied_to_region,
+			sto_position,
+			sto_velocity,
+			sto_direction,
+			sto_rectangle_collider,
+			sto_directional_animation,
+		): ProjectileStorages,
+	) -> Entity {
+		entities
+			.build_entity()
+			.with(Projectile::new(config, faction), sto_projectile)
+			.with(Removal::new(TiedToRegion), sto_removal_tied_to_region)
+			.with(position, sto_position)
+			.with(Velocity::in_direction(direction, config.speed), sto_velocity)
+			.with(direction, sto_direction)
+			.with(Projectile::compute_collider(&direction), sto_rectangle_collider)
+			.with(DirectionalAnimation::new(
+				sprite_sheet,
+				vec!(DirectionalFrame {
+					up: 0,
+					down: 1,
+					left: 2,
+					right: 3,
+					duration: None,
+				}),
+			), sto_directional_animation)
+			.build()
+	}
+
+	/// Computes the collider of a projectile flying in the given direction.
+	pub fn compute_collider(direction: &Direction) -> RectangleCollider {
+		match direction {
+			Direction::Up | Direction::Down => RectangleCollider {
+				half_width: PROJECTILE_HALF_WIDTH,
+				half_height: PROJECTILE_HALF_LENGTH,
+			},
+			Direction::Left | Direction::Right => RectangleCollider {
+				half_width: PROJECTILE_HALF_LENGTH,
+				half_height: PROJECTILE_HALF_WIDTH,
+			},
+		}
+	}
+}
+
+pub enum ProjectileStopped {
+	/// The projectile should be destroyed.
+	Yes,
+	/// The projectile keeps flying.
+	No,
+}
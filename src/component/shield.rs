@@ -4,10 +4,38 @@ use crate::{
 };
 
 use amethyst::ecs::{Component, DenseVecStorage, Entity};
+use serde::Deserialize;
 
+/// When a shield sends projectiles back the way they came, instead of stopping them.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Deserialize)]
+pub enum ShieldReflect {
+	Never,
+	/// Only projectiles that hit within `frames` frames of raising the shield are reflected.
+	WhenTimed {
+		frames: u32,
+	},
+	/// Every projectile that hits the raised shield is reflected, as with an upgraded shield.
+	Always,
+}
+
+/// Used for reading a shield type's tuning from a file.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Deserialize)]
+pub struct ShieldConfig {
+	pub reflect: ShieldReflect,
+}
+
+/// A shield that blocks hits from the front while its bearer holds it raised. Blocking costs stamina, and running
+/// out of stamina breaks the bearer's guard for a while. Depending on its type, a shield may instead reflect
+/// projectiles back at their shooter, which costs no stamina.
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
 pub struct Shield {
+	config: ShieldConfig,
 	bearer_id: Entity,
+	raised: bool,
+	/// The number of frames the shield has been held raised.
+	raised_frames: u32,
+	stamina: u32,
+	guard_broken_frames_left: u32,
 }
 
 impl Component for Shield {
@@ -15,9 +43,17 @@ impl Component for Shield {
 }
 
 impl Shield {
+	/// `config` The tuning of this type of shield.
 	/// `bearer_id` The entity ID of the character that bears this shield.
-	pub fn new(bearer_id: Entity) -> Self {
-		Self { bearer_id }
+	pub fn new(config: ShieldConfig, bearer_id: Entity) -> Self {
+		Self {
+			config,
+			bearer_id,
+			raised: false,
+			raised_frames: 0,
+			stamina: SHIELD_MAX_STAMINA,
+			guard_broken_frames_left: 0,
+		}
 	}
 
 	/// The entity ID of the character that bears this shield.
@@ -25,6 +61,51 @@ impl Shield {
 		self.bearer_id
 	}
 
+	/// Whether the shield is raised and able to block.
+	pub fn is_raised(&self) -> bool {
+		self.raised
+	}
+
+	/// Raises or lowers the shield. It stays lowered while the bearer's guard is broken.
+	pub fn set_raised(&mut self, raised: bool) {
+		let raised = raised && self.guard_broken_frames_left == 0;
+		if raised && !self.raised {
+			self.raised_frames = 0;
+		}
+		self.raised = raised;
+	}
+
+	/// Whether a projectile hitting the raised shield now would be reflected rather than blocked.
+	pub fn reflects(&self) -> bool {
+		match self.config.reflect {
+			ShieldReflect::Never => false,
+			ShieldReflect::WhenTimed { frames } => self.raised_frames < frames,
+			ShieldReflect::Always => true,
+		}
+	}
+
+	/// Spends stamina on blocking a hit, breaking the bearer's guard if the shield runs out.
+	pub fn block(&mut self) {
+		self.stamina = self.stamina.saturating_sub(SHIELD_BLOCK_COST);
+		if self.stamina == 0 {
+			self.raised = false;
+			self.guard_broken_frames_left = SHIELD_GUARD_BREAK_FRAMES;
+		}
+	}
+
+	/// Times how long the shield has been raised, recovers from a guard break, and regains stamina while lowered.
+	/// To be called once per frame.
+	pub fn update(&mut self) {
+		if self.raised {
+			self.raised_frames = self.raised_frames.saturating_add(1);
+		}
+		if self.guard_broken_frames_left > 0 {
+			self.guard_broken_frames_left -= 1;
+		} else if !self.raised && self.stamina < SHIELD_MAX_STAMINA {
+			self.stamina += 1;
+		}
+	}
+
 	/// Computes the position of the attack based on its bearer's orientation.
 	pub fn compute_position(
 		bearer_position: &Position,
@@ -1,12 +1,38 @@
-use crate::component::{Direction, Position, collider::RectangleCollider};
+use crate::component::{
+	animation::{DirectionalAnimation, DirectionalFrame},
+	collider::{HalfDiskCollider, RectangleCollider},
+	Damage,
+	Direction,
+	Faction,
+	Position,
+	removal::{TiedToEntity, TiedToRegion},
+};
 
-use amethyst::ecs::{Component, DenseVecStorage, Entity};
+use amethyst::{
+	assets::Handle,
+	ecs::{Component, DenseVecStorage, Entities, Entity, WriteStorage},
+	renderer::SpriteSheet,
+	utils::removal::Removal,
+};
 
 use std::collections::HashSet;
 
+/// The component storages a slash attack entity is built into.
+pub type SlashAttackStorages<'s, 'a> = (
+	&'s mut WriteStorage<'a, SlashAttack>,
+	&'s mut WriteStorage<'a, Removal<TiedToRegion>>,
+	&'s mut WriteStorage<'a, Removal<TiedToEntity>>,
+	&'s mut WriteStorage<'a, Position>,
+	&'s mut WriteStorage<'a, Direction>,
+	&'s mut WriteStorage<'a, HalfDiskCollider>,
+	&'s mut WriteStorage<'a, DirectionalAnimation>,
+);
+
 #[derive(Eq, PartialEq, Clone, Debug)]
 pub struct SlashAttack {
 	source_id: Entity,
+	faction: Faction,
+	damage: Damage,
 	/// IDs of characters this attack has already damaged.
 	already_hit_ids: HashSet<Entity>,
 }
@@ -17,9 +43,13 @@ impl Component for SlashAttack {
 
 impl SlashAttack {
 	/// `source_id` The entity ID of the character that spawned this slash attack.
-	pub fn new(source_id: Entity) -> Self {
+	/// `faction` The faction of the character that spawned this slash attack.
+	/// `damage` The damage dealt by this slash attack.
+	pub fn new(source_id: Entity, faction: Faction, damage: Damage) -> Self {
 		Self {
 			source_id,
+			faction,
+			damage,
 			already_hit_ids: HashSet::new(),
 		}
 	}
@@ -29,6 +59,64 @@ impl SlashAttack {
 		self.source_id
 	}
 
+	/// The faction of the character that spawned this slash attack.
+	pub fn faction(&self) -> Faction {
+		self.faction
+	}
+
+	/// The damage dealt by this slash attack, before resistances.
+	pub fn damage(&self) -> Damage {
+		self.damage
+	}
+
+	/// Starts a slash attack by the character `source_id` at `position` facing `direction` and returns its entity ID.
+	/// `faction` - The faction of the attacking character.
+	/// `damage` - The damage dealt by the attack.
+	/// `radius` - The reach of the attack.
+	/// `sprite_sheet` - The slash attack sprite sheet.
+	pub fn spawn(
+		entities: &Entities,
+		sprite_sheet: Handle<SpriteSheet>,
+		source_id: Entity,
+		faction: Faction,
+		damage: Damage,
+		radius: f32,
+		position: Position,
+		direction: Direction,
+		(
+			sto_slash_attack,
+			sto_removal_tied_to_region,
+			sto_removal_tied_to_entity,
+			sto_position,
+			sto_direction,
+			sto_half_disk_collider,
+			sto_directional_animation,
+		): SlashAttackStorages,
+	) -> Entity {
+		entities
+			.build_entity()
+			.with(SlashAttack::new(source_id, faction, damage), sto_slash_attack)
+			.with(Removal::new(TiedToRegion), sto_removal_tied_to_region)
+			.with(Removal::new(TiedToEntity(source_id)), sto_removal_tied_to_entity)
+			.with(position, sto_position)
+			.with(direction, sto_direction)
+			.with(HalfDiskCollider {
+				radius,
+				direction,
+			}, sto_half_disk_collider)
+			.with(DirectionalAnimation::new(
+				sprite_sheet,
+				vec!(DirectionalFrame {
+					up: 0,
+					down: 1,
+					left: 2,
+					right: 3,
+					duration: None,
+				}),
+			), sto_directional_animation)
+			.build()
+	}
+
 	/// Checks whether the entity with the given ID has already been hit by this slash attack.
 	pub fn has_been_hit(&self, id: Entity) -> bool {
 		self.already_hit_ids.contains(&id)
@@ -0,0 +1,9 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// The index of the region data entry that spawned this entity, used to remember changes to the region.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct SpawnIndex(pub usize);
+
+impl Component for SpawnIndex {
+	type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,106 @@
+use crate::{
+	component::{
+		collider::DiskCollider,
+		Damage,
+		Faction,
+		Position,
+		removal::{TiedToEntity, TiedToRegion},
+	},
+	constants::*,
+};
+
+use amethyst::{
+	ecs::{Component, DenseVecStorage, Entities, Entity, WriteStorage},
+	utils::removal::Removal,
+};
+
+use std::collections::HashSet;
+
+/// The component storages a spin attack entity is built into.
+pub type SpinAttackStorages<'s, 'a> = (
+	&'s mut WriteStorage<'a, SpinAttack>,
+	&'s mut WriteStorage<'a, Removal<TiedToRegion>>,
+	&'s mut WriteStorage<'a, Removal<TiedToEntity>>,
+	&'s mut WriteStorage<'a, Position>,
+	&'s mut WriteStorage<'a, DiskCollider>,
+);
+
+/// A charged sword attack that sweeps a full circle around its source, hitting each character in reach once.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct SpinAttack {
+	source_id: Entity,
+	faction: Faction,
+	damage: Damage,
+	/// IDs of characters this attack has already damaged.
+	already_hit_ids: HashSet<Entity>,
+}
+
+impl Component for SpinAttack {
+	type Storage = DenseVecStorage<Self>;
+}
+
+impl SpinAttack {
+	/// `source_id` The entity ID of the character that spawned this spin attack.
+	/// `faction` The faction of the character that spawned this spin attack.
+	/// `damage` The damage dealt by this spin attack.
+	pub fn new(source_id: Entity, faction: Faction, damage: Damage) -> Self {
+		Self {
+			source_id,
+			faction,
+			damage,
+			already_hit_ids: HashSet::new(),
+		}
+	}
+
+	/// The entity ID of the character that spawned this spin attack.
+	pub fn source_id(&self) -> Entity {
+		self.source_id
+	}
+
+	/// The faction of the character that spawned this spin attack.
+	pub fn faction(&self) -> Faction {
+		self.faction
+	}
+
+	/// The damage dealt by this spin attack, before resistances.
+	pub fn damage(&self) -> Damage {
+		self.damage
+	}
+
+	/// Starts a spin attack by the character `source_id`, centered on `position`, and returns its entity ID.
+	/// `faction` - The faction of the attacking character.
+	/// `damage` - The damage dealt by the attack.
+	pub fn spawn(
+		entities: &Entities,
+		source_id: Entity,
+		faction: Faction,
+		damage: Damage,
+		position: Position,
+		(
+			sto_spin_attack,
+			sto_removal_tied_to_region,
+			sto_removal_tied_to_entity,
+			sto_position,
+			sto_disk_collider,
+		): SpinAttackStorages,
+	) -> Entity {
+		entities
+			.build_entity()
+			.with(SpinAttack::new(source_id, faction, damage), sto_spin_attack)
+			.with(Removal::new(TiedToRegion), sto_removal_tied_to_region)
+			.with(Removal::new(TiedToEntity(source_id)), sto_removal_tied_to_entity)
+			.with(position, sto_position)
+			.with(DiskCollider { radius: SPIN_ATTACK_RADIUS }, sto_disk_collider)
+			.build()
+	}
+
+	/// Checks whether the entity with the given ID has already been hit by this spin attack.
+	pub fn has_been_hit(&self, id: Entity) -> bool {
+		self.already_hit_ids.contains(&id)
+	}
+
+	/// Marks the entity with the given ID as having been hit by this spin attack.
+	pub fn mark_as_hit(&mut self, id: Entity) {
+		self.already_hit_ids.insert(id);
+	}
+}
@@ -1,13 +1,39 @@
 use crate::{
-	component::{Direction, Position, collider::RectangleCollider},
+	component::{
+		animation::{DirectionalAnimation, DirectionalFrame},
+		collider::RectangleCollider,
+		Damage,
+		Direction,
+		Faction,
+		Position,
+		removal::{TiedToEntity, TiedToRegion},
+	},
 	constants::*,
 };
 
-use amethyst::ecs::{Component, DenseVecStorage, Entity};
+use amethyst::{
+	assets::Handle,
+	ecs::{Component, DenseVecStorage, Entities, Entity, WriteStorage},
+	renderer::SpriteSheet,
+	utils::removal::Removal,
+};
+
+/// The component storages a thrust attack entity is built into.
+pub type ThrustAttackStorages<'s, 'a> = (
+	&'s mut WriteStorage<'a, ThrustAttack>,
+	&'s mut WriteStorage<'a, Removal<TiedToRegion>>,
+	&'s mut WriteStorage<'a, Removal<TiedToEntity>>,
+	&'s mut WriteStorage<'a, Position>,
+	&'s mut WriteStorage<'a, Direction>,
+	&'s mut WriteStorage<'a, RectangleCollider>,
+	&'s mut WriteStorage<'a, DirectionalAnimation>,
+);
 
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
 pub struct ThrustAttack {
 	source_id: Entity,
+	faction: Faction,
+	damage: Damage,
 	is_active: bool,
 }
 
@@ -17,9 +43,13 @@ impl Component for ThrustAttack {
 
 impl ThrustAttack {
 	/// `source_id` The entity ID of the character that spawned this thrust attack.
-	pub fn new(source_id: Entity) -> Self {
+	/// `faction` The faction of the character that spawned this thrust attack.
+	/// `damage` The damage dealt by this thrust attack.
+	pub fn new(source_id: Entity, faction: Faction, damage: Damage) -> Self {
 		Self {
 			source_id,
+			faction,
+			damage,
 			is_active: true,
 		}
 	}
@@ -29,6 +59,60 @@ impl ThrustAttack {
 		self.source_id
 	}
 
+	/// The faction of the character that spawned this thrust attack.
+	pub fn faction(&self) -> Faction {
+		self.faction
+	}
+
+	/// The damage dealt by this thrust attack, before resistances.
+	pub fn damage(&self) -> Damage {
+		self.damage
+	}
+
+	/// Starts a thrust attack by the character `source_id` at `position` facing `direction` and returns its entity
+	/// ID.
+	/// `faction` - The faction of the attacking character.
+	/// `damage` - The damage dealt by the attack.
+	/// `sprite_sheet` - The thrust attack sprite sheet.
+	pub fn spawn(
+		entities: &Entities,
+		sprite_sheet: Handle<SpriteSheet>,
+		source_id: Entity,
+		faction: Faction,
+		damage: Damage,
+		position: Position,
+		direction: Direction,
+		(
+			sto_thrust_attack,
+			sto_removal_tied_to_region,
+			sto_removal_tied_to_entity,
+			sto_position,
+			sto_direction,
+			sto_rectangle_collider,
+			sto_directional_animation,
+		): ThrustAttackStorages,
+	) -> Entity {
+		entities
+			.build_entity()
+			.with(ThrustAttack::new(source_id, faction, damage), sto_thrust_attack)
+			.with(Removal::new(TiedToRegion), sto_removal_tied_to_region)
+			.with(Removal::new(TiedToEntity(source_id)), sto_removal_tied_to_entity)
+			.with(position, sto_position)
+			.with(direction, sto_direction)
+			.with(ThrustAttack::compute_collider(&direction), sto_rectangle_collider)
+			.with(DirectionalAnimation::new(
+				sprite_sheet,
+				vec!(DirectionalFrame {
+					up: 0,
+					down: 1,
+					left: 2,
+					right: 3,
+					duration: None,
+				}),
+			), sto_directional_animation)
+			.build()
+	}
+
 	/// Whether this thrust attack is able to hit a character.
 	pub fn is_active(&self) -> bool {
 		self.is_active
@@ -1,3 +1,5 @@
+use crate::component::{Direction, Position};
+
 use amethyst::ecs::{Component, DenseVecStorage};
 use serde::Deserialize;
 
@@ -11,6 +13,31 @@ impl Component for Velocity {
 	type Storage = DenseVecStorage<Self>;
 }
 
+impl Velocity {
+	/// The velocity of something moving from `from` straight toward `to` at `speed`, or zero if they coincide.
+	pub fn toward(from: &Position, to: &Position, speed: f32) -> Self {
+		let distance = from.distance_to(to);
+		if distance == 0.0 {
+			Self::default()
+		} else {
+			Self {
+				x: (to.x - from.x) * speed / distance,
+				y: (to.y - from.y) * speed / distance,
+			}
+		}
+	}
+
+	/// The velocity of something moving in `direction` at `speed`.
+	pub fn in_direction(direction: Direction, speed: f32) -> Self {
+		match direction {
+			Direction::Up => Self { x: 0.0, y: speed },
+			Direction::Down => Self { x: 0.0, y: -speed },
+			Direction::Left => Self { x: -speed, y: 0.0 },
+			Direction::Right => Self { x: speed, y: 0.0 },
+		}
+	}
+}
+
 impl Default for Velocity {
 	fn default() -> Velocity {
 		Velocity {
@@ -0,0 +1,121 @@
+//! Gameplay settings read from `config/gameplay.ron` and the command line.
+
+use crate::constants::*;
+
+use amethyst::config::Config;
+use serde::{Deserialize, Serialize};
+
+use std::path::{Path, PathBuf};
+
+/// Settings that affect gameplay rather than presentation. Settings missing from the file take their defaults.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameplayConfig {
+	/// The seed for the gameplay random number generator. If `None`, a random seed is chosen at startup.
+	pub seed: Option<u64>,
+	/// If set, the session's input is recorded and saved as a replay to this path on exit.
+	pub record_path: Option<PathBuf>,
+	/// If set, the replay at this path is played back and verified instead of reading player input.
+	pub replay_path: Option<PathBuf>,
+	/// Where the game is saved on region transitions and on exit. If `None`, the game is not saved.
+	pub save_path: Option<PathBuf>,
+	/// If set, the game resumes from the save at this path instead of starting a new game.
+	pub load_path: Option<PathBuf>,
+	/// When removed region entities come back.
+	pub respawn: RespawnPolicies,
+	/// The filename of the region a new game starts in.
+	pub starting_region: String,
+	/// The index of the entrance a new game starts at, within the starting region.
+	pub starting_entrance_idx: usize,
+}
+
+impl Default for GameplayConfig {
+	fn default() -> Self {
+		Self {
+			seed: None,
+			record_path: None,
+			replay_path: None,
+			save_path: None,
+			load_path: None,
+			respawn: RespawnPolicies::default(),
+			starting_region: STARTING_REGION.to_string(),
+			starting_entrance_idx: STARTING_ENTRANCE_IDX,
+		}
+	}
+}
+
+/// When an entity removed from a region by the hero is spawned again.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum RespawnPolicy {
+	/// The entity stays removed for good.
+	Never,
+	/// The entity returns when the hero rests, i.e. when a saved game is resumed.
+	OnRest,
+	/// The entity returns once the hero has entered its region this many times since it was removed, or on rest.
+	AfterEntries(u32),
+}
+
+/// Respawn policies for each type of region entity.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct RespawnPolicies {
+	pub hearts: RespawnPolicy,
+	pub enemies: RespawnPolicy,
+}
+
+impl Default for RespawnPolicies {
+	fn default() -> Self {
+		Self {
+			hearts: RespawnPolicy::OnRest,
+			enemies: RespawnPolicy::AfterEntries(1),
+		}
+	}
+}
+
+impl GameplayConfig {
+	/// Loads settings from the file at `path`, then overrides them with any given in `args`. Returns the
+	/// settings and the arguments that were not consumed.
+	pub fn from_path_and_args<P, I>(path: P, args: I) -> amethyst::Result<(Self, Vec<String>)>
+	where
+		P: AsRef<Path>,
+		I: IntoIterator<Item = String>,
+	{
+		let mut config = <Self as Config>::load(path)?;
+		let remaining = config.apply_args(args)?;
+		Ok((config, remaining))
+	}
+
+	/// Overrides settings with any given on the command line. Returns the arguments that were not consumed.
+	/// Recognized options: `--seed <u64>`, `--record <path>`, `--replay <path>`,
+	/// `--load <path>`.
+	pub fn apply_args<I: IntoIterator<Item = String>>(&mut self, args: I) -> amethyst::Result<Vec<String>> {
+		let mut remaining = Vec::new();
+		let mut args = args.into_iter();
+		while let Some(arg) = args.next() {
+			match arg.as_str() {
+				"--seed" => {
+					let seed = args.next().ok_or_else(|| amethyst::Error::from_string("--seed requires a value"))?;
+					self.seed = Some(seed.parse()?);
+				},
+				"--record" => {
+					let path = args.next().ok_or_else(|| amethyst::Error::from_string("--record requires a path"))?;
+					self.record_path = Some(path.into());
+				},
+				"--replay" => {
+					let path = args.next().ok_or_else(|| amethyst::Error::from_string("--replay requires a path"))?;
+					self.replay_path = Some(path.into());
+				},
+				"--load" => {
+					let path = args.next().ok_or_else(|| amethyst::Error::from_string("--load requires a path"))?;
+					self.load_path = Some(path.into());
+				},
+				_ => remaining.push(arg),
+			}
+		}
+		Ok(remaining)
+	}
+
+	/// The configured seed, or a fresh random one if none was configured.
+	pub fn seed_or_random(&self) -> u64 {
+		self.seed.unwrap_or_else(rand::random)
+	}
+}
@@ -2,10 +2,15 @@
 /// The size of a tile in the world.
 pub const TILE_SIZE: f32 = 40.0;
 
-/// The base maximum health of the hero.
-pub const HERO_BASE_HEALTH: i32 = 20;
-/// The base maximum health of enemies.
-pub const ENEMY_BASE_HEALTH: i32 = 3;
+/// The filename of the region a new game starts in, unless configured otherwise.
+pub const STARTING_REGION: &str = "test.ron";
+/// The index of the entrance a new game starts at, within the starting region, unless configured otherwise.
+pub const STARTING_ENTRANCE_IDX: usize = 1;
+
+/// The number of frames the hero's death animation lasts.
+pub const HERO_DEATH_FRAMES: u32 = 60;
+/// The number of frames between quarter turns of the hero's death animation.
+pub const HERO_DEATH_SPIN_PERIOD: u32 = 5;
 
 /// The shortest half-extent of a sword thrust's collider.
 pub const THRUST_ATTACK_HALF_WIDTH: f32 = 6.0;
@@ -13,34 +18,43 @@ pub const THRUST_ATTACK_HALF_WIDTH: f32 = 6.0;
 pub const THRUST_ATTACK_HALF_LENGTH: f32 = 20.0;
 /// The radius of a sword slash attack.
 pub const SLASH_ATTACK_RADIUS: f32 = 40.0;
+/// The radius of a charged spin attack, measured from the center of the spinning character.
+pub const SPIN_ATTACK_RADIUS: f32 = 60.0;
 /// The shortest half-extent of a shield's collider.
 pub const SHIELD_HALF_WIDTH: f32 = 6.0;
 /// The longest half-extent of a shield's collider.
 pub const SHIELD_HALF_LENGTH: f32 = 20.0;
-/// The shortest half-extent of an arrow attack's collider.
-pub const ARROW_ATTACK_HALF_WIDTH: f32 = 6.0;
-/// The longest half-extent of an arrow attack's collider.
-pub const ARROW_ATTACK_HALF_LENGTH: f32 = 20.0;
-/// The minimum number of frames an archer AI will wait before shooting an arrow.
-pub const MIN_ARROW_DELAY: u32 = 150;
-/// The maximum number of frames an archer AI will wait before shooting an arrow.
-pub const MAX_ARROW_DELAY: u32 = 300;
-/// The speed of a launched arrow.
-pub const ARROW_SPEED: f32 = 10.0;
+/// The stamina of a fully rested shield. Stamina recovers by one per frame while the shield is lowered.
+pub const SHIELD_MAX_STAMINA: u32 = 90;
+/// The stamina a shield loses for each blocked hit.
+pub const SHIELD_BLOCK_COST: u32 = 30;
+/// The number of frames a shield can't be raised after its stamina runs out.
+pub const SHIELD_GUARD_BREAK_FRAMES: u32 = 90;
+/// The shortest half-extent of a projectile's collider.
+pub const PROJECTILE_HALF_WIDTH: f32 = 6.0;
+/// The longest half-extent of a projectile's collider.
+pub const PROJECTILE_HALF_LENGTH: f32 = 20.0;
+/// The color an enemy is tinted while telegraphing an attack.
+pub const TELEGRAPH_TINT: [f32; 3] = [1.0, 0.5, 0.5];
+/// The color the hero is tinted while holding a charged spin attack.
+pub const CHARGED_TINT: [f32; 3] = [0.6, 0.8, 1.0];
+/// The speed of an arrow shot from the hero's bow.
+pub const BOW_ARROW_SPEED: f32 = 10.0;
 
-/// The speed at which a character is knocked away when hit.
-pub const KNOCKBACK_SPEED: f32 = 12.5;
-/// The number of frames knockback lasts.
-pub const KNOCKBACK_FRAMES: u32 = 5;
+/// The number of frames after using an item before another item can be used.
+pub const ITEM_COOLDOWN_FRAMES: u32 = 20;
+/// The number of frames between placing a bomb and its explosion.
+pub const BOMB_FUSE_FRAMES: u32 = 90;
+/// The distance from a bomb within which characters are hit by its explosion.
+pub const BOMB_BLAST_RADIUS: f32 = 1.5 * TILE_SIZE;
+/// The speed of a thrown boomerang, in both directions.
+pub const BOOMERANG_SPEED: f32 = 8.0;
+/// The number of frames a boomerang flies outward before returning.
+pub const BOOMERANG_OUTWARD_FRAMES: u32 = 20;
+/// The half-extent of a boomerang's square collider.
+pub const BOOMERANG_HALF_SIZE: f32 = 10.0;
 
-/// The number of frames a character is invulnerable after being damaged.
-pub const INVULNERABLE_FRAMES: u32 = 60;
 /// The period of the blinking animation during invulnerability, in frames.
 pub const INVULNERABLE_BLINK_PERIOD: u32 = 2;
 /// How bright a character should be during the dim part of the invulnerability blinking animation.
 pub const INVULNERABLE_BLINK_LUMINANCE: f32 = 0.75;
-
-/// The width of a heart pickup in the world.
-pub const HEART_WIDTH: f32 = 26.0;
-/// The height of a heart pickup in the world.
-pub const HEART_HEIGHT: f32 = 24.0;
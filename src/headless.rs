@@ -0,0 +1,179 @@
+//! Windowless simulation of the gameplay state, for running gameplay in CI and automated tests.
+
+use crate::{
+	component::{Direction, Health, Hero, HeroState, Position},
+	config::{GameplayConfig, RespawnPolicies},
+	resource::{GameRng, InputScript, Replay, SpriteSheets, Textures},
+	state,
+	system::ScriptedInput,
+};
+
+use amethyst::{
+	assets::{AssetStorage, Loader},
+	core::ArcThreadPool,
+	ecs::{Dispatcher, Entity},
+	prelude::*,
+	renderer::{SpriteSheet, Texture},
+	utils::application_root_dir,
+	window::ScreenDimensions,
+};
+use rayon::ThreadPoolBuilder;
+
+use std::sync::Arc;
+
+/// The screen dimensions reported to systems that expect a window.
+const SCREEN_WIDTH: u32 = 800;
+const SCREEN_HEIGHT: u32 = 600;
+
+/// An empty region, so that nothing but the hero's own actions affects the hero in tests.
+#[cfg(test)]
+pub const ARENA: &str = "arena.ron";
+
+/// Runs the same dispatcher as `state::Playing` against a world with stub asset handles.
+pub struct Headless<'a, 'b> {
+	world: World,
+	dispatcher: Dispatcher<'a, 'b>,
+	hero_id: Entity,
+}
+
+impl<'a, 'b> Headless<'a, 'b> {
+	/// Creates a world in the same initial state as `state::Playing`, without loading any textures, and starts the
+	/// hero at `entrance_idx` in `region`.
+	/// `seed` - The seed for the gameplay random number generator.
+	pub fn with_start(seed: u64, region: &str, entrance_idx: usize) -> amethyst::Result<Self> {
+		Self::with_start_and_respawn(seed, region, entrance_idx, RespawnPolicies::default())
+	}
+
+	/// Like `with_start`, but with the given respawn policies rather than the defaults.
+	fn with_start_and_respawn(
+		seed: u64,
+		region: &str,
+		entrance_idx: usize,
+		respawn: RespawnPolicies,
+	) -> amethyst::Result<Self> {
+		let app_root = application_root_dir()?;
+		let mut world = World::new();
+
+		// Insert the resources normally provided by the application and its bundles.
+		let pool: ArcThreadPool = Arc::new(ThreadPoolBuilder::new().build()?);
+		world.insert(pool.clone());
+		world.insert(Loader::new(app_root.join("assets"), pool));
+		world.insert(AssetStorage::<Texture>::new());
+		world.insert(AssetStorage::<SpriteSheet>::new());
+		world.insert(ScreenDimensions::new(SCREEN_WIDTH, SCREEN_HEIGHT, 1.0));
+		world.insert(GameRng::new(seed));
+		world.insert(GameplayConfig { respawn, ..GameplayConfig::default() });
+
+		let dispatcher = state::build_dispatcher(&mut world, ScriptedInput);
+
+		// Use stub textures and sprite sheets.
+		world.insert(Textures::stub(&world));
+		world.insert(SpriteSheets::stub(&world));
+
+		let hero_id = state::start_game(&mut world, region, entrance_idx);
+		world.maintain();
+
+		Ok(Self { world, dispatcher, hero_id })
+	}
+
+	/// Replaces the input script driving the hero. Playback starts on the next frame.
+	pub fn set_script(&mut self, script: InputScript) {
+		self.world.insert(script);
+	}
+
+	/// Plays back `replay` from the start of a fresh world, failing with a `Desync` error at the first frame whose
+	/// checksum does not match the recording.
+	pub fn verify_replay(replay: &Replay) -> amethyst::Result<()> {
+		let mut headless = Self::with_start_and_respawn(
+			replay.seed,
+			&replay.region,
+			replay.entrance_idx,
+			replay.respawn,
+		)?;
+		headless.set_script(replay.input_script());
+		for frame in 0..replay.frames.len() {
+			headless.step(1);
+			replay.verify_frame(frame, &headless.world)?;
+		}
+		Ok(())
+	}
+
+	/// Runs `frames` fixed frames of gameplay. Once the current input script is finished, no actions are held.
+	pub fn step(&mut self, frames: u32) {
+		for _ in 0..frames {
+			self.dispatcher.dispatch(&self.world);
+			state::take_exits(&mut self.world);
+			self.world.maintain();
+		}
+	}
+
+	/// The simulated world, for inspecting arbitrary components and resources.
+	pub fn world(&self) -> &World {
+		&self.world
+	}
+
+	/// The entity ID of the hero.
+	pub fn hero_id(&self) -> Entity {
+		self.hero_id
+	}
+
+	/// The hero's current position, if the hero is still alive.
+	pub fn hero_position(&self) -> Option<Position> {
+		self.world().read_storage::<Position>().get(self.hero_id()).copied()
+	}
+
+	/// The direction the hero is facing, if the hero is still alive.
+	pub fn hero_direction(&self) -> Option<Direction> {
+		self.world().read_storage::<Direction>().get(self.hero_id()).copied()
+	}
+
+	/// The hero's current health, if the hero is still alive.
+	pub fn hero_health(&self) -> Option<Health> {
+		self.world().read_storage::<Health>().get(self.hero_id()).copied()
+	}
+
+	/// The hero's current state, if the hero is still alive.
+	pub fn hero_state(&self) -> Option<HeroState> {
+		self.world().read_storage::<Hero>().get(self.hero_id()).map(|hero| hero.state)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{constants::*, input_bindings::Actions, resource::InputStep};
+
+	#[test]
+	fn places_hero_at_starting_entrance() {
+		let headless = Headless::with_start(0, STARTING_REGION, STARTING_ENTRANCE_IDX).unwrap();
+		assert_eq!(headless.hero_position(), Some(Position { x: 8.0 * TILE_SIZE, y: -9.0 * TILE_SIZE }));
+		assert_eq!(headless.hero_direction(), Some(Direction::Left));
+		let health = headless.hero_health().unwrap();
+		assert_eq!(health.current(), health.max());
+		assert_eq!(headless.hero_state(), Some(HeroState::FreelyMoving));
+	}
+
+	#[test]
+	fn hero_stands_still_without_input() {
+		let mut headless = Headless::with_start(0, ARENA, 0).unwrap();
+		let start = headless.hero_position();
+		headless.step(30);
+		assert_eq!(headless.hero_position(), start);
+		assert_eq!(headless.hero_direction(), Some(Direction::Down));
+		assert_eq!(headless.hero_state(), Some(HeroState::FreelyMoving));
+	}
+
+	#[test]
+	fn script_moves_hero_while_it_lasts() {
+		let mut headless = Headless::with_start(0, ARENA, 0).unwrap();
+		let start = headless.hero_position().unwrap();
+		headless.set_script(InputScript::new(vec!(InputStep { frames: 10, actions: vec!(Actions::Up) })));
+		headless.step(10);
+		let end = Position { x: start.x, y: start.y + 50.0 };
+		assert_eq!(headless.hero_position(), Some(end));
+		assert_eq!(headless.hero_direction(), Some(Direction::Up));
+		// Once the script is finished, no actions are held.
+		headless.step(10);
+		assert_eq!(headless.hero_position(), Some(end));
+	}
+}
@@ -10,7 +10,7 @@ use serde::{Serialize, Deserialize};
 pub struct Axes;
 
 /// The set of input actions the player can perform.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Actions {
 	Up,
 	Down,
@@ -19,6 +19,25 @@ pub enum Actions {
 	Primary,
 	Secondary,
 	Strafe,
+	Pause,
+	NextItem,
+	Roll,
+}
+
+impl Actions {
+	/// Every action, in declaration order.
+	pub const ALL: [Actions; 10] = [
+		Actions::Up,
+		Actions::Down,
+		Actions::Left,
+		Actions::Right,
+		Actions::Primary,
+		Actions::Secondary,
+		Actions::Strafe,
+		Actions::Pause,
+		Actions::NextItem,
+		Actions::Roll,
+	];
 }
 
 impl Display for Axes {
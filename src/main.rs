@@ -1,3 +1,6 @@
+use crate::config::GameplayConfig;
+
+#[cfg(not(feature = "empty"))]
 use amethyst::{
 	core::transform::TransformBundle,
 	input::{InputBundle, StringBindings},
@@ -8,16 +11,23 @@ use amethyst::{
 		RenderingBundle,
 	},
 	ui::{RenderUi, UiBundle},
-	utils::application_root_dir,
 };
+use amethyst::utils::application_root_dir;
+
+#[cfg(not(feature = "empty"))]
+use std::path::PathBuf;
 
 mod component;
+mod config;
 mod constants;
+#[cfg(any(test, feature = "empty"))]
+mod headless;
 mod input_bindings;
 mod resource;
 mod state;
 mod system;
 
+#[cfg(not(feature = "empty"))]
 fn main() -> amethyst::Result<()> {
 	amethyst::start_logger(Default::default());
 
@@ -25,6 +35,12 @@ fn main() -> amethyst::Result<()> {
 	let resources = app_root.join("assets");
 	let display_config = app_root.join("config/display_config.ron");
 	let key_bindings_path = app_root.join("config/input.ron");
+	let (gameplay_config, _) = GameplayConfig::from_path_and_args(
+		app_root.join("config/gameplay.ron"),
+		std::env::args().skip(1),
+	)?;
+	let seed = gameplay_config.seed_or_random();
+	log::info!("Gameplay RNG seed: {}", seed);
 
 	let game_data = { GameDataBuilder::default()
 		// Transform
@@ -45,7 +61,85 @@ fn main() -> amethyst::Result<()> {
 		)?
 	};
 
-	Application::new(resources, state::Playing::new(), game_data)?.run();
+	// Command-line options that choose how to play skip the main menu.
+	let playing = if let Some(replay_path) = &gameplay_config.replay_path {
+		Some(state::Playing::replaying(resource::Replay::load(replay_path)?))
+	} else if let Some(record_path) = &gameplay_config.record_path {
+		Some(state::Playing::recording(record_path.clone()))
+	} else if let Some(load_path) = &gameplay_config.load_path {
+		Some(state::Playing::from_save(resource::SaveData::load(load_path)?))
+	} else {
+		None
+	};
+
+	match playing {
+		Some(playing) => run(resources, playing, game_data, seed, gameplay_config),
+		None => run(resources, state::MainMenu::new(), game_data, seed, gameplay_config),
+	}
+}
+
+/// Runs the application, starting in `initial_state`.
+#[cfg(not(feature = "empty"))]
+fn run<S: SimpleState + 'static>(
+	resources: PathBuf,
+	initial_state: S,
+	game_data: GameDataBuilder<'static, 'static>,
+	seed: u64,
+	gameplay_config: GameplayConfig,
+) -> amethyst::Result<()> {
+	Application::build(resources, initial_state)?
+		.with_resource(resource::GameRng::new(seed))
+		.with_resource(gameplay_config)
+		.build(game_data)?
+		.run();
+
+	Ok(())
+}
+
+/// Without a renderer, simulates the number of frames given as the first argument and logs the hero's final state.
+/// If a second argument is given, it is the path of an input script to drive the hero with. The RNG seed can be set
+/// with `--seed <u64>`. With `--replay <path>`, instead verifies the given replay and exits.
+#[cfg(feature = "empty")]
+fn main() -> amethyst::Result<()> {
+	/// The number of frames to simulate if none is specified.
+	const DEFAULT_FRAMES: u32 = 600;
+
+	amethyst::start_logger(Default::default());
+
+	let (gameplay_config, args) = GameplayConfig::from_path_and_args(
+		application_root_dir()?.join("config/gameplay.ron"),
+		std::env::args().skip(1),
+	)?;
+	if let Some(replay_path) = gameplay_config.replay_path {
+		let replay = resource::Replay::load(&replay_path)?;
+		headless::Headless::verify_replay(&replay)?;
+		log::info!("Replay of {} frames verified", replay.frames.len());
+		return Ok(());
+	}
+	let seed = gameplay_config.seed_or_random();
+	log::info!("Gameplay RNG seed: {}", seed);
+
+	let frames = match args.get(0) {
+		Some(frames) => frames.parse()?,
+		None => DEFAULT_FRAMES,
+	};
+	let mut headless = headless::Headless::with_start(
+		seed,
+		&gameplay_config.starting_region,
+		gameplay_config.starting_entrance_idx,
+	)?;
+	if let Some(script_path) = args.get(1) {
+		headless.set_script(resource::InputScript::load(script_path)?);
+	}
+	headless.step(frames);
+	log::info!(
+		"After {} frames: position = {:?}, direction = {:?}, health = {:?}, state = {:?}",
+		frames,
+		headless.hero_position(),
+		headless.hero_direction(),
+		headless.hero_health(),
+		headless.hero_state(),
+	);
 
 	Ok(())
 }
@@ -0,0 +1,46 @@
+use crate::input_bindings::{Actions, InputBindings};
+
+use amethyst::input::InputHandler;
+use serde::{Deserialize, Serialize};
+
+/// The set of actions held down during the current frame. Gameplay systems read player input from this
+/// resource rather than from the `InputHandler`, so that input can also come from a script.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ActionState {
+	/// One bit per action, indexed by the action's discriminant.
+	bits: u16,
+}
+
+impl ActionState {
+	/// Creates an action state with exactly `actions` held down.
+	pub fn new(actions: &[Actions]) -> Self {
+		let mut action_state = Self::default();
+		for action in actions {
+			action_state.set(*action, true);
+		}
+		action_state
+	}
+
+	/// Reads the current state of every action from `input`. Unbound actions are treated as released.
+	pub fn from_input(input: &InputHandler<InputBindings>) -> Self {
+		let mut action_state = Self::default();
+		for action in Actions::ALL.iter() {
+			action_state.set(*action, input.action_is_down(action).unwrap_or(false));
+		}
+		action_state
+	}
+
+	/// Whether `action` is held down.
+	pub fn is_down(&self, action: Actions) -> bool {
+		self.bits & (1 << action as u16) != 0
+	}
+
+	/// Sets whether `action` is held down.
+	pub fn set(&mut self, action: Actions, down: bool) {
+		if down {
+			self.bits |= 1 << action as u16;
+		} else {
+			self.bits &= !(1 << action as u16);
+		}
+	}
+}
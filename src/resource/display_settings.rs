@@ -0,0 +1,5 @@
+/// Resource tracking display settings changed at runtime.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Default)]
+pub struct DisplaySettings {
+	pub fullscreen: bool,
+}
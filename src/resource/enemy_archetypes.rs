@@ -0,0 +1,79 @@
+use crate::{
+	component::{
+		animation::DirectionalFrame,
+		collider::RectangleCollider,
+		Damage,
+		Direction,
+		Faction,
+	},
+	resource::{ComponentData, Prefab},
+};
+
+use ron::de::from_reader;
+use serde::Deserialize;
+
+use std::{collections::HashMap, fs::File};
+
+/// A kind of enemy, read from `assets/enemies/<name>.ron`.
+#[derive(PartialEq, Clone, Debug, Deserialize)]
+pub struct EnemyArchetype {
+	/// Maximum (and starting) health.
+	pub health: i32,
+	/// Movement speed while acting on behaviors.
+	pub speed: f32,
+	/// The damage dealt to a hero by running into the enemy.
+	pub contact_damage: Damage,
+	pub collider: RectangleCollider,
+	/// The file stem of the enemy's sprite sheet and texture under `assets/sprites`.
+	pub sprite_sheet: String,
+	/// Components to attach besides those every enemy has, e.g. behaviors.
+	pub components: Vec<ComponentData>,
+}
+
+impl EnemyArchetype {
+	/// Reads the archetype called `name` from its file and checks that its components' tuning is usable.
+	pub fn load(name: &str) -> amethyst::Result<Self> {
+		let path = format!("assets/enemies/{}.ron", name);
+		let file = File::open(&path)?;
+		let archetype: Self = from_reader(file)?;
+		for component in &archetype.components {
+			component.validate()?;
+		}
+		Ok(archetype)
+	}
+
+	/// The prefab of an enemy of this kind: the components every enemy has, built from the archetype's fields,
+	/// followed by the archetype's own components.
+	pub fn prefab(&self) -> Prefab {
+		let mut components = vec!(
+			ComponentData::Enemy { speed: self.speed, contact_damage: self.contact_damage },
+			ComponentData::Faction(Faction::Enemy),
+			ComponentData::Health(self.health),
+			ComponentData::Direction(Direction::Down),
+			ComponentData::Velocity,
+			ComponentData::RectangleCollider(self.collider),
+			ComponentData::DirectionalAnimation {
+				sprite_sheet: self.sprite_sheet.clone(),
+				frames: vec!(DirectionalFrame { up: 0, down: 1, left: 2, right: 3, duration: None }),
+			},
+			ComponentData::TiedToRegion,
+		);
+		components.extend(self.components.iter().cloned());
+		Prefab { components, children: Vec::new() }
+	}
+}
+
+/// Enemy archetypes loaded so far, by name.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct EnemyArchetypes {
+	archetypes: HashMap<String, EnemyArchetype>,
+}
+
+impl EnemyArchetypes {
+	/// Gets the archetype called `name`, loading it the first time it's requested.
+	pub fn get(&mut self, name: &str) -> &EnemyArchetype {
+		self.archetypes.entry(name.to_string()).or_insert_with(|| {
+			EnemyArchetype::load(name).unwrap_or_else(|error| panic!("Error in enemy archetype file {}: {}", name, error))
+		})
+	}
+}
@@ -0,0 +1,41 @@
+use rand::{rngs::StdRng, Error, RngCore, SeedableRng};
+
+/// The source of randomness for all gameplay systems. Seeding it identically reproduces a session exactly.
+#[derive(Clone, Debug)]
+pub struct GameRng {
+	seed: u64,
+	rng: StdRng,
+}
+
+impl GameRng {
+	/// Creates a random number generator from `seed`.
+	pub fn new(seed: u64) -> Self {
+		Self {
+			seed,
+			rng: StdRng::seed_from_u64(seed),
+		}
+	}
+
+	/// The seed this generator was created from.
+	pub fn seed(&self) -> u64 {
+		self.seed
+	}
+}
+
+impl RngCore for GameRng {
+	fn next_u32(&mut self) -> u32 {
+		self.rng.next_u32()
+	}
+
+	fn next_u64(&mut self) -> u64 {
+		self.rng.next_u64()
+	}
+
+	fn fill_bytes(&mut self, dest: &mut [u8]) {
+		self.rng.fill_bytes(dest)
+	}
+
+	fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+		self.rng.try_fill_bytes(dest)
+	}
+}
@@ -7,6 +7,8 @@ use amethyst::{
 pub struct Hud {
 	pub max_hearts: i32,
 	pub heart_images: Vec<Entity>,
+	/// The image of the selected item, once created.
+	pub item_image: Option<Entity>,
 }
 
 impl Hud {
@@ -14,6 +16,7 @@ impl Hud {
 		Self {
 			max_hearts: 0,
 			heart_images: Vec::new(),
+			item_image: None,
 		}
 	}
 }
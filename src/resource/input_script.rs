@@ -0,0 +1,57 @@
+use crate::{
+	input_bindings::Actions,
+	resource::ActionState,
+};
+
+#[cfg(any(test, feature = "empty"))]
+use ron::de::from_reader;
+use serde::Deserialize;
+
+#[cfg(any(test, feature = "empty"))]
+use std::{fs::File, path::Path};
+
+/// A per-frame sequence of held actions, used to drive the hero without a player.
+#[derive(Eq, PartialEq, Clone, Debug, Default, Deserialize)]
+pub struct InputScript {
+	steps: Vec<InputStep>,
+	/// The number of frames already played back.
+	#[serde(skip)]
+	frames_played: u32,
+}
+
+/// A run of consecutive frames during which the same actions are held.
+#[derive(Eq, PartialEq, Clone, Debug, Deserialize)]
+pub struct InputStep {
+	/// The number of frames this step lasts.
+	pub frames: u32,
+	/// The actions held down during this step. Any other actions are released.
+	pub actions: Vec<Actions>,
+}
+
+impl InputScript {
+	/// Creates a script from a list of steps.
+	pub fn new(steps: Vec<InputStep>) -> Self {
+		Self { steps, frames_played: 0 }
+	}
+
+	/// Loads a script from a RON file at `path`.
+	#[cfg(any(test, feature = "empty"))]
+	pub fn load<P: AsRef<Path>>(path: P) -> amethyst::Result<Self> {
+		let file = File::open(path)?;
+		Ok(from_reader(file)?)
+	}
+
+	/// Advances playback by one frame and returns that frame's action state. No actions are held once the
+	/// script is finished.
+	pub fn next_frame(&mut self) -> ActionState {
+		let mut frame = self.frames_played;
+		self.frames_played += 1;
+		for step in &self.steps {
+			if frame < step.frames {
+				return ActionState::new(&step.actions);
+			}
+			frame -= step.frames;
+		}
+		ActionState::default()
+	}
+}
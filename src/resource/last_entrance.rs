@@ -0,0 +1,5 @@
+/// Resource tracking the entrance of the current region through which the hero most recently arrived.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Default)]
+pub struct LastEntrance {
+	pub idx: usize,
+}
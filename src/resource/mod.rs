@@ -1,17 +1,39 @@
+mod action_state;
 mod camera;
+mod display_settings;
+mod enemy_archetypes;
 mod entrance;
 mod exit;
+mod game_rng;
 mod hud;
+mod input_script;
+mod last_entrance;
+mod pathfinder;
+mod prefabs;
 mod region;
 mod region_data;
+mod region_states;
+mod replay;
+mod save_data;
 mod sprite_sheets;
 mod textures;
 
+pub use action_state::ActionState;
 pub use camera::Camera;
+pub use display_settings::DisplaySettings;
+pub use enemy_archetypes::{EnemyArchetype, EnemyArchetypes};
 pub use entrance::Entrance;
 pub use exit::Exit;
+pub use game_rng::GameRng;
 pub use hud::Hud;
+pub use input_script::{InputScript, InputStep};
+pub use last_entrance::LastEntrance;
+pub use pathfinder::Pathfinder;
+pub use prefabs::{ComponentData, Prefab, PrefabInstance, Prefabs};
 pub use region::{CurrentRegion, Region};
 pub use region_data::{EnemyData, RegionData};
+pub use region_states::RegionStates;
+pub use replay::{checksum, Desync, Replay, ReplayFrame};
+pub use save_data::SaveData;
 pub use sprite_sheets::SpriteSheets;
 pub use textures::Textures;
@@ -0,0 +1,153 @@
+use crate::{component::TileCoords, resource::Region};
+
+use std::{
+	cmp::Reverse,
+	collections::{BinaryHeap, HashMap},
+};
+
+/// The maximum number of goals to remember paths to before forgetting the least recently used one.
+const CACHE_CAPACITY: usize = 64;
+
+/// Finds shortest paths between tiles of a region, moving orthogonally between tiles that don't block movement.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Pathfinder {
+	row_count: usize,
+	col_count: usize,
+	/// Whether each tile blocks movement, in row-major order.
+	blocked: Vec<bool>,
+	/// Paths found so far, by goal.
+	cache: HashMap<TileCoords, GoalPaths>,
+	/// Incremented with each query, to track when each goal was last used.
+	clock: u64,
+}
+
+/// The known shortest paths to one goal.
+#[derive(Eq, PartialEq, Clone, Debug, Default)]
+struct GoalPaths {
+	/// The next tile on the way to the goal from each tile, or `None` if the goal can't be reached from it.
+	next_tiles: HashMap<TileCoords, Option<TileCoords>>,
+	/// The value of the pathfinder's clock when the goal was last queried.
+	last_used: u64,
+}
+
+impl Pathfinder {
+	/// Creates a pathfinder over the tiles of `region`.
+	pub fn new(region: &Region) -> Self {
+		let row_count = region.row_count();
+		let col_count = region.col_count();
+		let blocked = (0..row_count * col_count).map(|idx| {
+			region.blocks_movement_at(TileCoords { row: idx / col_count, col: idx % col_count })
+		}).collect();
+		Self {
+			row_count,
+			col_count,
+			blocked,
+			cache: HashMap::new(),
+			clock: 0,
+		}
+	}
+
+	/// Finds a shortest path from `start` to `goal`, as the tiles to step through after `start`, ending with `goal`.
+	/// Returns `None` if `goal` can't be reached.
+	pub fn find_path(&mut self, start: TileCoords, goal: TileCoords) -> Option<Vec<TileCoords>> {
+		self.clock += 1;
+		let known = self.cache.get(&goal).map_or(false, |goal_paths| goal_paths.next_tiles.contains_key(&start));
+		if !known {
+			let path = self.search(start, goal);
+			if !self.cache.contains_key(&goal) && self.cache.len() >= CACHE_CAPACITY {
+				let least_recently_used = *self.cache.iter()
+					.min_by_key(|(_, goal_paths)| goal_paths.last_used)
+					.unwrap()
+					.0;
+				self.cache.remove(&least_recently_used);
+			}
+			// Every tile along a shortest path also has a shortest path to the goal from there.
+			let next_tiles = &mut self.cache.entry(goal).or_default().next_tiles;
+			match path {
+				Some(path) => {
+					let mut tile = start;
+					for next_tile in path {
+						next_tiles.insert(tile, Some(next_tile));
+						tile = next_tile;
+					}
+				},
+				None => {
+					next_tiles.insert(start, None);
+				},
+			}
+		}
+		let goal_paths = self.cache.get_mut(&goal).unwrap();
+		goal_paths.last_used = self.clock;
+		// Follow the known next tiles to the goal.
+		let mut path = Vec::new();
+		let mut tile = start;
+		while tile != goal {
+			tile = goal_paths.next_tiles[&tile]?;
+			path.push(tile);
+		}
+		Some(path)
+	}
+
+	/// A* search from `start` to `goal`, using Manhattan distance as the heuristic.
+	fn search(&self, start: TileCoords, goal: TileCoords) -> Option<Vec<TileCoords>> {
+		if !self.is_open(start) || !self.is_open(goal) {
+			return None;
+		}
+		let heuristic = |tile: TileCoords| {
+			(tile.row as isize - goal.row as isize).abs() as usize + (tile.col as isize - goal.col as isize).abs() as usize
+		};
+		let start_idx = self.index(start);
+		let goal_idx = self.index(goal);
+		let mut cost_so_far = vec!(usize::MAX; self.blocked.len());
+		let mut came_from = vec!(None; self.blocked.len());
+		let mut frontier = BinaryHeap::new();
+		cost_so_far[start_idx] = 0;
+		frontier.push(Reverse((heuristic(start), start_idx)));
+		while let Some(Reverse((_, idx))) = frontier.pop() {
+			if idx == goal_idx {
+				// Walk back from the goal to reconstruct the path.
+				let mut path = Vec::new();
+				let mut current = goal_idx;
+				while current != start_idx {
+					path.push(self.tile_coords(current));
+					current = came_from[current].unwrap();
+				}
+				path.reverse();
+				return Some(path);
+			}
+			for neighbor in self.open_neighbors(self.tile_coords(idx)) {
+				let neighbor_idx = self.index(neighbor);
+				let cost = cost_so_far[idx] + 1;
+				if cost < cost_so_far[neighbor_idx] {
+					cost_so_far[neighbor_idx] = cost;
+					came_from[neighbor_idx] = Some(idx);
+					frontier.push(Reverse((cost + heuristic(neighbor), neighbor_idx)));
+				}
+			}
+		}
+		None
+	}
+
+	/// The orthogonally adjacent tiles of `tile` that are in bounds and don't block movement.
+	fn open_neighbors(&self, tile: TileCoords) -> impl Iterator<Item = TileCoords> + '_ {
+		let TileCoords { row, col } = tile;
+		let up = row.checked_sub(1).map(|row| TileCoords { row, col });
+		let down = Some(TileCoords { row: row + 1, col });
+		let left = col.checked_sub(1).map(|col| TileCoords { row, col });
+		let right = Some(TileCoords { row, col: col + 1 });
+		vec!(up, down, left, right).into_iter().flatten().filter(move |neighbor| self.is_open(*neighbor))
+	}
+
+	/// Whether `tile` is in bounds and doesn't block movement.
+	fn is_open(&self, tile: TileCoords) -> bool {
+		tile.row < self.row_count && tile.col < self.col_count && !self.blocked[self.index(tile)]
+	}
+
+	fn index(&self, tile: TileCoords) -> usize {
+		tile.row * self.col_count + tile.col
+	}
+
+	fn tile_coords(&self, idx: usize) -> TileCoords {
+		TileCoords { row: idx / self.col_count, col: idx % self.col_count }
+	}
+}
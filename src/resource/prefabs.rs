@@ -0,0 +1,220 @@
+use crate::{
+	component::{
+		animation::{DirectionalAnimation, DirectionalFrame, SimpleAnimation, SimpleFrame},
+		behavior::{BehaviorNode, BehaviorTree, Waypoints},
+		collider::RectangleCollider,
+		Combo,
+		ComboConfig,
+		Damage,
+		DamageType,
+		Direction,
+		Enemy,
+		Faction,
+		Health,
+		Heart,
+		Hero,
+		HeroState,
+		HitReaction,
+		Item,
+		ItemSlot,
+		Position,
+		removal::{TiedToEntity, TiedToRegion},
+		Resistances,
+		Shield,
+		ShieldConfig,
+		TileCoords,
+		Velocity,
+		WeaponDamage,
+	},
+	resource::SpriteSheets,
+};
+
+use amethyst::{
+	ecs::Entity,
+	prelude::*,
+	utils::removal::Removal,
+};
+use ron::de::from_reader;
+use serde::Deserialize;
+
+use std::{collections::HashMap, fs::File, mem::discriminant};
+
+/// A bundle of components, read from `assets/prefabs/<name>.ron`.
+#[derive(PartialEq, Clone, Debug, Deserialize)]
+pub struct Prefab {
+	pub components: Vec<ComponentData>,
+	/// Names of prefabs to build along with this one, at the same position. Their `Shield` and `TiedToParent`
+	/// components refer to the entity built from this prefab.
+	#[serde(default)]
+	pub children: Vec<String>,
+}
+
+impl Prefab {
+	/// Reads the prefab called `name` from its file and checks that its components' tuning is usable.
+	pub fn load(name: &str) -> amethyst::Result<Self> {
+		let path = format!("assets/prefabs/{}.ron", name);
+		let file = File::open(&path)?;
+		let prefab: Self = from_reader(file)?;
+		for component in &prefab.components {
+			component.validate()?;
+		}
+		Ok(prefab)
+	}
+}
+
+/// Used for reading a single component from a file.
+#[derive(PartialEq, Clone, Debug, Deserialize)]
+pub enum ComponentData {
+	Hero,
+	Enemy {
+		/// Movement speed while acting on behaviors.
+		speed: f32,
+		/// The damage dealt to a hero by running into them.
+		contact_damage: Damage,
+	},
+	Heart {
+		/// The amount of health restored on pickup.
+		heal: u32,
+	},
+	Faction(Faction),
+	/// Maximum (and starting) health.
+	Health(i32),
+	HitReaction(HitReaction),
+	/// Damage multipliers by damage type.
+	Resistances(HashMap<DamageType, f32>),
+	WeaponDamage(WeaponDamage),
+	/// The sword combos a character can perform.
+	Combo(ComboConfig),
+	Direction(Direction),
+	/// Starts at rest.
+	Velocity,
+	RectangleCollider(RectangleCollider),
+	DirectionalAnimation {
+		/// The file stem of the sprite sheet and texture under `assets/sprites`.
+		sprite_sheet: String,
+		frames: Vec<DirectionalFrame>,
+	},
+	SimpleAnimation {
+		/// The file stem of the sprite sheet and texture under `assets/sprites`.
+		sprite_sheet: String,
+		frames: Vec<SimpleFrame>,
+	},
+	/// The root node of a behavior tree.
+	BehaviorTree(BehaviorNode),
+	/// The tiles a behavior tree's `Patrol` node visits in a loop, usually given per instance in region data.
+	Waypoints(Vec<TileCoords>),
+	ItemSlot(Item),
+	/// A shield borne by the parent entity.
+	Shield(ShieldConfig),
+	/// Removed when the current region is unloaded.
+	TiedToRegion,
+	/// Removed when the parent entity is deleted.
+	TiedToParent,
+}
+
+impl ComponentData {
+	/// Checks that the component's tuning is usable.
+	pub fn validate(&self) -> amethyst::Result<()> {
+		match self {
+			ComponentData::BehaviorTree(root) => root.validate(),
+			_ => Ok(()),
+		}
+	}
+}
+
+/// Used for reading a placement of a prefab in a region from a file.
+#[derive(PartialEq, Clone, Debug, Deserialize)]
+pub struct PrefabInstance {
+	/// The name of the prefab, i.e. its path under `assets/prefabs` without the extension.
+	pub prefab: String,
+	pub location: TileCoords,
+	/// Components that replace the prefab's components of the same kind, or are added if it has none.
+	#[serde(default)]
+	pub overrides: Vec<ComponentData>,
+}
+
+/// Prefabs loaded so far, by name.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Prefabs {
+	prefabs: HashMap<String, Prefab>,
+}
+
+impl Prefabs {
+	/// Gets the prefab called `name`, loading it the first time it's requested.
+	pub fn get(&mut self, name: &str) -> &Prefab {
+		self.prefabs.entry(name.to_string()).or_insert_with(|| {
+			Prefab::load(name).unwrap_or_else(|error| panic!("Error in prefab file {}: {}", name, error))
+		})
+	}
+
+	/// Builds an entity from the prefab called `name` at `position`, along with its children. Returns the ID of the
+	/// entity built from the prefab itself.
+	/// `overrides` - Components replacing the prefab's components of the same kind.
+	pub fn instantiate(world: &mut World, name: &str, position: Position, overrides: &[ComponentData]) -> Entity {
+		let prefab = world.write_resource::<Prefabs>().get(name).clone();
+		Self::build(world, prefab, position, overrides)
+	}
+
+	/// Like `instantiate`, but builds `prefab` itself rather than one looked up by name, e.g. the prefab of an enemy
+	/// archetype.
+	pub fn build(world: &mut World, prefab: Prefab, position: Position, overrides: &[ComponentData]) -> Entity {
+		Self::build_with_parent(world, prefab, position, overrides, None)
+	}
+
+	fn build_with_parent(
+		world: &mut World,
+		prefab: Prefab,
+		position: Position,
+		overrides: &[ComponentData],
+		parent_id: Option<Entity>,
+	) -> Entity {
+		// Apply overrides.
+		let mut components = prefab.components;
+		for component_override in overrides {
+			match components.iter_mut().find(|component| discriminant(*component) == discriminant(component_override)) {
+				Some(component) => *component = component_override.clone(),
+				None => components.push(component_override.clone()),
+			}
+		}
+		// Build the entity.
+		let require_parent_id = || parent_id.expect("Prefab component requires a parent");
+		let mut builder = world.create_entity().with(position);
+		for component in components {
+			builder = match component {
+				ComponentData::Hero => builder.with(Hero { state: HeroState::FreelyMoving, roll_cooldown_frames_left: 0 }),
+				ComponentData::Enemy { speed, contact_damage } => builder.with(Enemy { speed, contact_damage }),
+				ComponentData::Heart { heal } => builder.with(Heart { heal }),
+				ComponentData::Faction(faction) => builder.with(faction),
+				ComponentData::Health(max) => builder.with(Health::new(max)),
+				ComponentData::HitReaction(hit_reaction) => builder.with(hit_reaction),
+				ComponentData::Resistances(multipliers) => builder.with(Resistances(multipliers)),
+				ComponentData::WeaponDamage(weapon_damage) => builder.with(weapon_damage),
+				ComponentData::Combo(config) => builder.with(Combo::new(config)),
+				ComponentData::Direction(direction) => builder.with(direction),
+				ComponentData::Velocity => builder.with(Velocity::default()),
+				ComponentData::RectangleCollider(collider) => builder.with(collider),
+				ComponentData::DirectionalAnimation { sprite_sheet, frames } => {
+					let sprite_sheet = SpriteSheets::get_or_load(builder.world, &sprite_sheet);
+					builder.with(DirectionalAnimation::new(sprite_sheet, frames))
+				},
+				ComponentData::SimpleAnimation { sprite_sheet, frames } => {
+					let sprite_sheet = SpriteSheets::get_or_load(builder.world, &sprite_sheet);
+					builder.with(SimpleAnimation::new(sprite_sheet, frames))
+				},
+				ComponentData::BehaviorTree(root) => builder.with(BehaviorTree::new(root)),
+				ComponentData::Waypoints(waypoints) => builder.with(Waypoints(waypoints)),
+				ComponentData::ItemSlot(item) => builder.with(ItemSlot::new(item)),
+				ComponentData::Shield(config) => builder.with(Shield::new(config, require_parent_id())),
+				ComponentData::TiedToRegion => builder.with(Removal::new(TiedToRegion)),
+				ComponentData::TiedToParent => builder.with(Removal::new(TiedToEntity(require_parent_id()))),
+			};
+		}
+		let id = builder.build();
+		// Build children.
+		for child in &prefab.children {
+			let child = world.write_resource::<Prefabs>().get(child).clone();
+			Self::build_with_parent(world, child, position, &[], Some(id));
+		}
+		id
+	}
+}
@@ -9,6 +9,7 @@ use crate::{
 	resource::{
 		Entrance,
 		Exit,
+		Pathfinder,
 		SpriteSheets,
 	},
 };
@@ -22,18 +23,24 @@ use amethyst::{
 };
 use nalgebra::Vector3;
 
+use std::iter;
+
 /// A set of tiles representing a game region, with links to other regions.
 #[derive(Eq, PartialEq, Clone, Debug)]
 pub struct Region {
+	filename: String,
 	row_count: usize,
 	col_count: usize,
 	tiles: Vec<Entity>,
+	/// Whether each tile blocks movement, in the same order as `tiles`.
+	blocked: Vec<bool>,
 	entrances: Vec<Entrance>,
 	exits: Vec<Exit>,
 }
 
 impl Region {
 	pub fn new(
+		filename: &str,
 		col_count: usize,
 		terrain: Vec<Terrain>,
 		entrances: Vec<Entrance>,
@@ -41,6 +48,7 @@ impl Region {
 		world: &mut World,
 	) -> Self {
 		let mut tiles: Vec<Entity> = Vec::new();
+		let blocked = terrain.iter().map(Terrain::blocks_movement).collect();
 		let row_count = terrain.len() / col_count;
 		let col_count = col_count;
 		for (i, terrain) in terrain.into_iter().enumerate() {
@@ -67,14 +75,39 @@ impl Region {
 			tiles.push(tile);
 		}
 		Self {
+			filename: filename.to_string(),
 			row_count,
 			col_count,
 			tiles,
+			blocked,
 			entrances,
 			exits,
 		}
 	}
 
+	/// The filename of this region, within the `assets/regions` directory.
+	pub fn filename(&self) -> &str {
+		&self.filename
+	}
+
+	/// The number of rows of tiles.
+	pub fn row_count(&self) -> usize {
+		self.row_count
+	}
+
+	/// The number of columns of tiles.
+	pub fn col_count(&self) -> usize {
+		self.col_count
+	}
+
+	/// Whether the tile at `tile_coords` blocks movement. Tiles outside the region block movement.
+	pub fn blocks_movement_at(&self, tile_coords: TileCoords) -> bool {
+		if tile_coords.row >= self.row_count || tile_coords.col >= self.col_count {
+			return true;
+		}
+		self.blocked[tile_coords.row * self.col_count + tile_coords.col]
+	}
+
 	/// The entrances into this region.
 	pub fn entrances(&self) -> &Vec<Entrance> {
 		&self.entrances
@@ -99,6 +132,34 @@ impl Region {
 		})
 	}
 
+	/// Whether a straight line from `from` to `to` crosses no tiles that block movement. Tiles outside the region
+	/// block sight.
+	pub fn has_line_of_sight(&self, sto_terrain: &ReadStorage<Terrain>, from: Position, to: Position) -> bool {
+		// Sample the line often enough not to skip over the corner of a tile.
+		const SAMPLES_PER_TILE: f32 = 4.0;
+		let dx = to.x - from.x;
+		let dy = to.y - from.y;
+		let steps = (f32::sqrt(dx.powi(2) + dy.powi(2)) / TILE_SIZE * SAMPLES_PER_TILE).ceil() as u32;
+		(0..=steps).all(|step| {
+			let t = if steps == 0 { 0.0 } else { step as f32 / steps as f32 };
+			let tile_coords: Option<TileCoords> = Position { x: from.x + t * dx, y: from.y + t * dy }.into();
+			tile_coords
+				.and_then(|tile_coords| self.terrain_at_tile_coords(sto_terrain, tile_coords))
+				.map_or(false, |terrain| !terrain.blocks_movement())
+		})
+	}
+
+	/// Whether `to` is in the same row or column of tiles as `from` and in sight of it.
+	pub fn has_line_of_fire(&self, sto_terrain: &ReadStorage<Terrain>, from: Position, to: Position) -> bool {
+		let from_tile: Option<TileCoords> = from.into();
+		let to_tile: Option<TileCoords> = to.into();
+		let lined_up = match (from_tile, to_tile) {
+			(Some(from_tile), Some(to_tile)) => from_tile.row == to_tile.row || from_tile.col == to_tile.col,
+			_ => false,
+		};
+		lined_up && self.has_line_of_sight(sto_terrain, from, to)
+	}
+
 	/// Gets the terrain at the given (`x`, `y`) coordinates, if any.
 	pub fn terrain_at_position(&self, sto_terrain: &ReadStorage<Terrain>, position: Position) -> Option<Terrain> {
 		let tile_coords: Option<TileCoords> = position.into();
@@ -108,10 +169,11 @@ impl Region {
 	}
 }
 
-/// Resource for accessing the currently active region.
+/// Resource for accessing the currently active region and finding paths through it.
 #[derive(Eq, PartialEq, Clone, Debug)]
 pub struct CurrentRegion {
 	region: Option<Region>,
+	pathfinder: Option<Pathfinder>,
 }
 
 impl CurrentRegion {
@@ -119,11 +181,13 @@ impl CurrentRegion {
 	pub fn new() -> Self {
 		Self {
 			region: None,
+			pathfinder: None,
 		}
 	}
 
-	/// Sets the current region.
+	/// Sets the current region, rebuilding the pathfinder for it.
 	pub fn set(&mut self, region: Region) {
+		self.pathfinder = Some(Pathfinder::new(&region));
 		self.region = Some(region);
 	}
 
@@ -131,6 +195,49 @@ impl CurrentRegion {
 	pub fn get(&self) -> &Region {
 		self.region.as_ref().unwrap()
 	}
+
+	/// Gets the pathfinder for the current region.
+	pub fn pathfinder_mut(&mut self) -> &mut Pathfinder {
+		self.pathfinder.as_mut().unwrap()
+	}
+
+	/// The position to head for next on the way from `from` to `to`: the center of the next tile on the shortest
+	/// path between their tiles, or `to` itself once it's in the next tile. If there is no path, this is `from`, so
+	/// that the character stands still instead of walking into a wall.
+	pub fn next_step(&mut self, from: &Position, to: &Position) -> Position {
+		let from_tile: Option<TileCoords> = (*from).into();
+		let to_tile: Option<TileCoords> = (*to).into();
+		match (from_tile, to_tile) {
+			(Some(from_tile), Some(to_tile)) => match self.pathfinder_mut().find_path(from_tile, to_tile) {
+				Some(path) if path.len() > 1 => path[0].into(),
+				Some(_) => *to,
+				None => *from,
+			},
+			_ => *from,
+		}
+	}
+
+	/// The position to head for next when getting away from `threat`: the center of whichever tile next to the one at
+	/// `from` is farthest from `threat`, skipping tiles that block movement. If no such tile is farther away than
+	/// `from`, this is `from`, so that a cornered character stands still instead of walking into a wall.
+	pub fn retreat_step(&self, from: &Position, threat: &Position) -> Position {
+		let from_tile: Option<TileCoords> = (*from).into();
+		let TileCoords { row, col } = match from_tile {
+			Some(from_tile) => from_tile,
+			None => return *from,
+		};
+		let up = row.checked_sub(1).map(|row| TileCoords { row, col });
+		let down = Some(TileCoords { row: row + 1, col });
+		let left = col.checked_sub(1).map(|col| TileCoords { row, col });
+		let right = Some(TileCoords { row, col: col + 1 });
+		vec!(up, down, left, right).into_iter()
+			.flatten()
+			.filter(|tile_coords| !self.get().blocks_movement_at(*tile_coords))
+			.map(Position::from)
+			.chain(iter::once(*from))
+			.max_by(|a, b| a.distance_to(threat).partial_cmp(&b.distance_to(threat)).unwrap())
+			.unwrap()
+	}
 }
 
 impl Default for CurrentRegion {
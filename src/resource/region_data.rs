@@ -3,24 +3,30 @@ use crate::{
 		Terrain,
 		TileCoords,
 	},
-	resource::{Entrance, Exit},
+	resource::{ComponentData, Entrance, Exit, PrefabInstance},
 };
 
 use serde::Deserialize;
 
 /// Used for reading region data from a file.
-#[derive(Eq, PartialEq, Clone, Debug, Deserialize)]
+#[derive(PartialEq, Clone, Debug, Deserialize)]
 pub struct RegionData {
 	pub col_count: usize,
 	pub terrain: Vec<Terrain>,
 	pub entrances: Vec<Entrance>,
 	pub exits: Vec<Exit>,
 	pub enemies: Vec<EnemyData>,
-	pub heart_locations: Vec<TileCoords>,
+	/// Other entities placed in the region, e.g. hearts.
+	pub prefabs: Vec<PrefabInstance>,
 }
 
 /// Used for reading enemy data from a file.
-#[derive(Eq, PartialEq, Clone, Debug, Deserialize)]
+#[derive(PartialEq, Clone, Debug, Deserialize)]
 pub struct EnemyData {
+	/// The name of the enemy's archetype, i.e. the file stem of its file in `assets/enemies`.
+	pub archetype: String,
 	pub location: TileCoords,
+	/// Components that replace the archetype's components of the same kind, or are added if it has none.
+	#[serde(default)]
+	pub overrides: Vec<ComponentData>,
 }
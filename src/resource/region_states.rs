@@ -0,0 +1,97 @@
+use crate::config::{RespawnPolicies, RespawnPolicy};
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+
+/// Remembers how the hero has changed each region they have visited, keyed by region filename.
+#[derive(Eq, PartialEq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RegionStates {
+	/// The filename of the current region.
+	current: String,
+	regions: BTreeMap<String, RegionChanges>,
+}
+
+/// Tracks which of a region's spawned entities have been removed by the hero, by `SpawnIndex`. Each removal is
+/// mapped to the number of times the hero had entered the region when it happened.
+#[derive(Eq, PartialEq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RegionChanges {
+	/// The number of times the hero has entered the region.
+	pub entries: u32,
+	/// Indices into the region's prefab instances of hearts that have been picked up.
+	pub collected_hearts: BTreeMap<usize, u32>,
+	/// Indices into the region's enemies of enemies that have been killed.
+	pub killed_enemies: BTreeMap<usize, u32>,
+}
+
+impl RegionStates {
+	/// Makes `region` the current region, counting the entry and then respawning any of its entities that are due
+	/// according to `policies`. Returns the changes that are still in effect.
+	pub fn enter(&mut self, region: &str, policies: &RespawnPolicies) -> &RegionChanges {
+		self.current = region.to_string();
+		let changes = self.current_mut();
+		changes.entries += 1;
+		let entries = changes.entries;
+		let due = |policy: RespawnPolicy, removed_at: u32| match policy {
+			RespawnPolicy::AfterEntries(count) => entries - removed_at >= count,
+			RespawnPolicy::Never | RespawnPolicy::OnRest => false,
+		};
+		changes.collected_hearts.retain(|_, removed_at| !due(policies.hearts, *removed_at));
+		changes.killed_enemies.retain(|_, removed_at| !due(policies.enemies, *removed_at));
+		changes
+	}
+
+	/// Respawns every removed entity whose policy is not `RespawnPolicy::Never`, in all regions.
+	pub fn rest(&mut self, policies: &RespawnPolicies) {
+		for changes in self.regions.values_mut() {
+			if policies.hearts != RespawnPolicy::Never {
+				changes.collected_hearts.clear();
+			}
+			if policies.enemies != RespawnPolicy::Never {
+				changes.killed_enemies.clear();
+			}
+		}
+	}
+
+	/// Records that the heart at `heart_idx` in the current region was picked up.
+	pub fn collect_heart(&mut self, heart_idx: usize) {
+		let changes = self.current_mut();
+		changes.collected_hearts.insert(heart_idx, changes.entries);
+	}
+
+	/// Records that the enemy at `enemy_idx` in the current region was killed.
+	pub fn kill_enemy(&mut self, enemy_idx: usize) {
+		let changes = self.current_mut();
+		changes.killed_enemies.insert(enemy_idx, changes.entries);
+	}
+
+	fn current_mut(&mut self) -> &mut RegionChanges {
+		self.regions.entry(self.current.clone()).or_default()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const POLICIES: RespawnPolicies = RespawnPolicies {
+		hearts: RespawnPolicy::AfterEntries(2),
+		enemies: RespawnPolicy::AfterEntries(1),
+	};
+
+	#[test]
+	fn removed_entities_respawn_after_their_region_is_entered_enough_times() {
+		let mut region_states = RegionStates::default();
+		region_states.enter("a.ron", &POLICIES);
+		region_states.collect_heart(0);
+		region_states.kill_enemy(0);
+		// Visiting another region doesn't count towards respawning the first region's entities.
+		region_states.enter("b.ron", &POLICIES);
+		let changes = region_states.enter("a.ron", &POLICIES);
+		assert!(changes.collected_hearts.contains_key(&0));
+		assert!(!changes.killed_enemies.contains_key(&0));
+		region_states.enter("b.ron", &POLICIES);
+		let changes = region_states.enter("a.ron", &POLICIES);
+		assert!(!changes.collected_hearts.contains_key(&0));
+	}
+}
@@ -0,0 +1,221 @@
+use crate::{
+	component::{Health, Position},
+	config::RespawnPolicies,
+	resource::{ActionState, InputScript, InputStep},
+	input_bindings::Actions,
+};
+
+use amethyst::{
+	ecs::Join,
+	prelude::*,
+};
+use ron::{de::from_reader, ser::to_string};
+use serde::{Deserialize, Serialize};
+
+use std::{
+	error::Error,
+	fmt::{self, Display},
+	fs::{self, File},
+	path::Path,
+};
+
+/// A recorded play session: everything needed to reproduce it exactly, plus per-frame checksums for
+/// detecting desyncs.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct Replay {
+	/// The seed of the gameplay random number generator.
+	pub seed: u64,
+	/// The filename of the starting region.
+	pub region: String,
+	/// The index of the entrance the hero started at.
+	pub entrance_idx: usize,
+	/// The respawn policies in effect during the session.
+	pub respawn: RespawnPolicies,
+	/// One entry per fixed frame.
+	pub frames: Vec<ReplayFrame>,
+}
+
+/// The recorded input and resulting state of a single fixed frame.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayFrame {
+	pub actions: ActionState,
+	/// The checksum of the world at the end of the frame. See `checksum`.
+	pub checksum: u64,
+}
+
+impl Replay {
+	/// Creates an empty replay of a session starting in `region` at `entrance_idx`.
+	pub fn new(seed: u64, region: &str, entrance_idx: usize, respawn: RespawnPolicies) -> Self {
+		Self {
+			seed,
+			region: region.to_string(),
+			entrance_idx,
+			respawn,
+			frames: Vec::new(),
+		}
+	}
+
+	/// Loads a replay from the file at `path`.
+	pub fn load<P: AsRef<Path>>(path: P) -> amethyst::Result<Self> {
+		let file = File::open(path)?;
+		Ok(from_reader(file)?)
+	}
+
+	/// Saves this replay to the file at `path`.
+	pub fn save<P: AsRef<Path>>(&self, path: P) -> amethyst::Result<()> {
+		fs::write(path, to_string(self)?)?;
+		Ok(())
+	}
+
+	/// Records the frame that was just dispatched in `world`.
+	pub fn record_frame(&mut self, world: &World) {
+		self.frames.push(ReplayFrame {
+			actions: *world.read_resource::<ActionState>(),
+			checksum: checksum(world),
+		});
+	}
+
+	/// Checks that the state of `world` after frame number `frame` matches the recording. Frames past the end of
+	/// the recording always match.
+	pub fn verify_frame(&self, frame: usize, world: &World) -> Result<(), Desync> {
+		match self.frames.get(frame) {
+			Some(recorded) => {
+				let actual = checksum(world);
+				if actual == recorded.checksum {
+					Ok(())
+				} else {
+					Err(Desync { frame, expected: recorded.checksum, actual })
+				}
+			},
+			None => Ok(()),
+		}
+	}
+
+	/// Converts the recorded actions into an input script that plays them back.
+	pub fn input_script(&self) -> InputScript {
+		let mut steps: Vec<(ActionState, u32)> = Vec::new();
+		for frame in &self.frames {
+			match steps.last_mut() {
+				Some((actions, frames)) if *actions == frame.actions => *frames += 1,
+				_ => steps.push((frame.actions, 1)),
+			}
+		}
+		InputScript::new(steps.into_iter().map(|(actions, frames)| InputStep {
+			frames,
+			actions: Actions::ALL.iter().copied().filter(|action| actions.is_down(*action)).collect(),
+		}).collect())
+	}
+}
+
+/// Computes a checksum of the position and health of every entity in `world`. The checksum doesn't depend on entity
+/// IDs or on the order entities are stored in.
+pub fn checksum(world: &World) -> u64 {
+	// FNV-1a, which unlike the standard library's hasher is guaranteed to be stable.
+	const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+	const PRIME: u64 = 0x0000_0100_0000_01b3;
+	let mut checksum: u64 = 0;
+	for (position, health) in (&world.read_storage::<Position>(), world.read_storage::<Health>().maybe()).join() {
+		let mut hash = OFFSET_BASIS;
+		let mut write = |value: u32| {
+			for byte in value.to_le_bytes().iter() {
+				hash ^= u64::from(*byte);
+				hash = hash.wrapping_mul(PRIME);
+			}
+		};
+		write(position.x.to_bits());
+		write(position.y.to_bits());
+		if let Some(health) = health {
+			write(health.current() as u32);
+			write(health.max() as u32);
+		}
+		// Summing the entities' hashes makes the order they're visited in irrelevant.
+		checksum = checksum.wrapping_add(hash);
+	}
+	checksum
+}
+
+/// A mismatch between a replay's recorded checksum and the replayed world.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Desync {
+	/// The zero-based index of the first frame that did not match.
+	pub frame: usize,
+	pub expected: u64,
+	pub actual: u64,
+}
+
+impl Display for Desync {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "replay desynced at frame {}: expected checksum {:#x}, got {:#x}", self.frame, self.expected, self.actual)
+	}
+}
+
+impl Error for Desync {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{constants::*, headless::Headless};
+
+	const SEED: u64 = 7;
+	const FRAMES: usize = 60;
+
+	/// Records a short session in the starting region, walking and attacking near the enemies there.
+	fn record() -> Replay {
+		let mut replay = Replay::new(SEED, STARTING_REGION, STARTING_ENTRANCE_IDX, RespawnPolicies::default());
+		let mut headless = Headless::with_start(SEED, STARTING_REGION, STARTING_ENTRANCE_IDX).unwrap();
+		headless.set_script(InputScript::new(vec!(
+			InputStep { frames: 20, actions: vec!(Actions::Up) },
+			InputStep { frames: 5, actions: vec!(Actions::Up, Actions::Primary) },
+			InputStep { frames: 35, actions: vec!(Actions::Left) },
+		)));
+		for _ in 0..FRAMES {
+			headless.step(1);
+			replay.record_frame(headless.world());
+		}
+		replay
+	}
+
+	#[test]
+	fn recorded_replay_verifies() {
+		let replay = record();
+		assert_eq!(replay.frames.len(), FRAMES);
+		Headless::verify_replay(&replay).unwrap();
+	}
+
+	#[test]
+	fn perturbed_frame_desyncs() {
+		const PERTURBED_FRAME: usize = 30;
+		let mut replay = record();
+		replay.frames[PERTURBED_FRAME].checksum ^= 1;
+		assert!(Headless::verify_replay(&replay).is_err());
+		// Replay by hand to check which frame is reported.
+		let mut headless = Headless::with_start(SEED, &replay.region, replay.entrance_idx).unwrap();
+		headless.set_script(replay.input_script());
+		for frame in 0..PERTURBED_FRAME {
+			headless.step(1);
+			assert_eq!(replay.verify_frame(frame, headless.world()), Ok(()));
+		}
+		headless.step(1);
+		let desync = replay.verify_frame(PERTURBED_FRAME, headless.world()).unwrap_err();
+		assert_eq!(desync.frame, PERTURBED_FRAME);
+		assert_eq!(desync.expected, replay.frames[PERTURBED_FRAME].checksum);
+		assert_eq!(desync.actual, replay.frames[PERTURBED_FRAME].checksum ^ 1);
+	}
+
+	#[test]
+	fn checksum_ignores_entity_ids_and_order() {
+		fn world_with(positions: &[Position]) -> World {
+			let mut world = World::new();
+			world.register::<Position>();
+			world.register::<Health>();
+			for position in positions {
+				world.create_entity().with(*position).build();
+			}
+			world
+		}
+		let a = Position { x: 1.0, y: 2.0 };
+		let b = Position { x: 3.0, y: 4.0 };
+		assert_eq!(checksum(&world_with(&[a, b])), checksum(&world_with(&[b, a])));
+		assert_ne!(checksum(&world_with(&[a, b])), checksum(&world_with(&[a, a])));
+	}
+}
@@ -0,0 +1,79 @@
+use crate::{
+	component::{Direction, Health, Hero, Item, ItemSlot, Position},
+	resource::{CurrentRegion, LastEntrance, RegionStates},
+};
+
+use amethyst::{
+	ecs::Join,
+	prelude::*,
+};
+use ron::{
+	de::from_reader,
+	ser::{to_string_pretty, PrettyConfig},
+};
+use serde::{Deserialize, Serialize};
+
+use std::{
+	fs::{self, File},
+	path::Path,
+};
+
+/// A saved game, read from and written to RON files.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct SaveData {
+	/// The filename of the region the hero is in.
+	pub region: String,
+	/// The index of the entrance through which the hero arrived in the current region.
+	pub entrance_idx: usize,
+	/// How the hero has changed each region.
+	pub region_states: RegionStates,
+	pub hero_position: Position,
+	pub hero_direction: Direction,
+	pub hero_health: Health,
+	/// The hero's selected item. Missing from saves made before items existed.
+	#[serde(default)]
+	pub hero_item: Item,
+}
+
+impl SaveData {
+	/// Captures the current game state from `world`, or `None` if there is no living hero.
+	pub fn from_world(world: &World) -> Option<Self> {
+		let sto_hero = world.read_storage::<Hero>();
+		let sto_position = world.read_storage::<Position>();
+		let sto_direction = world.read_storage::<Direction>();
+		let sto_health = world.read_storage::<Health>();
+		let sto_item_slot = world.read_storage::<ItemSlot>();
+		let (_hero, hero_position, hero_direction, hero_health, hero_item_slot) = (
+			&sto_hero,
+			&sto_position,
+			&sto_direction,
+			&sto_health,
+			sto_item_slot.maybe(),
+		).join().find(|(_hero, _position, _direction, health, _item_slot)| health.current() > 0)?;
+		Some(Self {
+			region: world.read_resource::<CurrentRegion>().get().filename().to_string(),
+			entrance_idx: world.read_resource::<LastEntrance>().idx,
+			region_states: world.read_resource::<RegionStates>().clone(),
+			hero_position: *hero_position,
+			hero_direction: *hero_direction,
+			hero_health: *hero_health,
+			hero_item: hero_item_slot.map_or_else(Item::default, |item_slot| item_slot.item),
+		})
+	}
+
+	/// Loads a saved game from the file at `path`.
+	pub fn load<P: AsRef<Path>>(path: P) -> amethyst::Result<Self> {
+		let file = File::open(path)?;
+		Ok(from_reader(file)?)
+	}
+
+	/// Writes this saved game to the file at `path`, creating its parent directory if needed.
+	pub fn save<P: AsRef<Path>>(&self, path: P) -> amethyst::Result<()> {
+		let path = path.as_ref();
+		if let Some(parent) = path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		fs::write(path, to_string_pretty(self, PrettyConfig::default())?)?;
+		Ok(())
+	}
+}
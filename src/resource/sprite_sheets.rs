@@ -4,22 +4,25 @@ use amethyst::{
 	assets::{AssetStorage, Handle, Loader},
 	prelude::*,
 	renderer::{
+		ImageFormat,
 		SpriteSheet,
 		SpriteSheetFormat,
+		Texture,
 	},
 };
 
+use std::collections::HashMap;
+
 /// Container for all the game's sprite sheets.
 #[derive(Eq, PartialEq, Clone, Debug)]
 pub struct SpriteSheets {
-	pub enemy: Handle<SpriteSheet>,
-	pub hero: Handle<SpriteSheet>,
 	pub slash_attack: Handle<SpriteSheet>,
 	pub thrust_attack: Handle<SpriteSheet>,
 	pub terrain: Handle<SpriteSheet>,
-	pub hearts: Handle<SpriteSheet>,
 	pub arrow_attack: Handle<SpriteSheet>,
-	pub shield: Handle<SpriteSheet>,
+	pub items: Handle<SpriteSheet>,
+	/// Every sprite sheet loaded so far, by name, including those above. Prefabs refer to sprite sheets by name.
+	named: HashMap<String, Handle<SpriteSheet>>,
 }
 
 impl SpriteSheets {
@@ -27,23 +30,78 @@ impl SpriteSheets {
 		let textures = world.read_resource::<Textures>();
 		let loader = world.read_resource::<Loader>();
 		let sprite_sheet_storage = &world.read_resource::<AssetStorage<SpriteSheet>>();
-		let load_sprite_sheet = |name, texture_handle| {
-			loader.load(
+		Self::from_named(vec!(
+			("character", textures.hero.clone()),
+			("slash_attack", textures.slash_attack.clone()),
+			("thrust_attack", textures.thrust_attack.clone()),
+			("terrain", textures.terrain.clone()),
+			("hearts", textures.hearts.clone()),
+			("arrow_attack", textures.arrow_attack.clone()),
+			("shield", textures.shield.clone()),
+			("items", textures.items.clone()),
+		).into_iter().map(|(name, texture_handle)| {
+			let sprite_sheet = loader.load(
 				format!("sprites/{}.ron", name),
 				SpriteSheetFormat(texture_handle),
 				(),
 				sprite_sheet_storage,
-			)
-		};
+			);
+			(name.to_string(), sprite_sheet)
+		}).collect())
+	}
+
+	/// Creates sprite sheet handles with no sprites, for running without a renderer. Expects a stub `Textures`
+	/// resource to be present already.
+	#[cfg(any(test, feature = "empty"))]
+	pub fn stub(world: &World) -> Self {
+		let textures = world.read_resource::<Textures>();
+		let loader = world.read_resource::<Loader>();
+		let sprite_sheet_storage = &world.read_resource::<AssetStorage<SpriteSheet>>();
+		Self::from_named(vec!(
+			("character", textures.hero.clone()),
+			("slash_attack", textures.slash_attack.clone()),
+			("thrust_attack", textures.thrust_attack.clone()),
+			("terrain", textures.terrain.clone()),
+			("hearts", textures.hearts.clone()),
+			("arrow_attack", textures.arrow_attack.clone()),
+			("shield", textures.shield.clone()),
+			("items", textures.items.clone()),
+		).into_iter().map(|(name, texture)| {
+			let sprite_sheet = loader.load_from_data(SpriteSheet { texture, sprites: Vec::new() }, (), sprite_sheet_storage);
+			(name.to_string(), sprite_sheet)
+		}).collect())
+	}
+
+	/// Picks out the sprite sheets used directly by systems from the named sprite sheets.
+	fn from_named(named: HashMap<String, Handle<SpriteSheet>>) -> Self {
 		Self {
-			enemy: load_sprite_sheet("character", textures.enemy.clone()),
-			hero: load_sprite_sheet("character", textures.hero.clone()),
-			slash_attack: load_sprite_sheet("slash_attack", textures.slash_attack.clone()),
-			thrust_attack: load_sprite_sheet("thrust_attack", textures.thrust_attack.clone()),
-			terrain: load_sprite_sheet("terrain", textures.terrain.clone()),
-			hearts: load_sprite_sheet("hearts", textures.hearts.clone()),
-			arrow_attack: load_sprite_sheet("arrow_attack", textures.arrow_attack.clone()),
-			shield: load_sprite_sheet("shield", textures.shield.clone()),
+			slash_attack: named["slash_attack"].clone(),
+			thrust_attack: named["thrust_attack"].clone(),
+			terrain: named["terrain"].clone(),
+			arrow_attack: named["arrow_attack"].clone(),
+			items: named["items"].clone(),
+			named,
 		}
 	}
+
+	/// Gets the sprite sheet called `name`, loading it and its texture from `sprites/<name>.ron` and
+	/// `sprites/<name>.png` the first time it's requested.
+	pub fn get_or_load(world: &World, name: &str) -> Handle<SpriteSheet> {
+		let mut sprite_sheets = world.write_resource::<SpriteSheets>();
+		sprite_sheets.named.entry(name.to_string()).or_insert_with(|| {
+			let loader = world.read_resource::<Loader>();
+			let texture = loader.load(
+				format!("sprites/{}.png", name),
+				ImageFormat::default(),
+				(),
+				&world.read_resource::<AssetStorage<Texture>>(),
+			);
+			loader.load(
+				format!("sprites/{}.ron", name),
+				SpriteSheetFormat(texture),
+				(),
+				&world.read_resource::<AssetStorage<SpriteSheet>>(),
+			)
+		}).clone()
+	}
 }
@@ -6,11 +6,12 @@ use amethyst::{
 		Texture,
 	},
 };
+#[cfg(any(test, feature = "empty"))]
+use amethyst::renderer::{rendy::texture::TextureBuilder, types::TextureData};
 
 /// Container for all the game's textures.
 #[derive(Eq, PartialEq, Clone, Debug)]
 pub struct Textures {
-	pub enemy: Handle<Texture>,
 	pub hero: Handle<Texture>,
 	pub slash_attack: Handle<Texture>,
 	pub thrust_attack: Handle<Texture>,
@@ -18,6 +19,7 @@ pub struct Textures {
 	pub hearts: Handle<Texture>,
 	pub arrow_attack: Handle<Texture>,
 	pub shield: Handle<Texture>,
+	pub items: Handle<Texture>,
 }
 
 impl Textures {
@@ -33,7 +35,6 @@ impl Textures {
 			)
 		};
 		Self {
-			enemy: load_texture("character"),
 			hero: load_texture("character"),
 			slash_attack: load_texture("slash_attack"),
 			thrust_attack: load_texture("thrust_attack"),
@@ -41,6 +42,27 @@ impl Textures {
 			hearts: load_texture("hearts"),
 			arrow_attack: load_texture("arrow_attack"),
 			shield: load_texture("shield"),
+			items: load_texture("items"),
+		}
+	}
+
+	/// Creates texture handles backed by empty texture data, for running without a renderer.
+	#[cfg(any(test, feature = "empty"))]
+	pub fn stub(world: &World) -> Self {
+		let loader = world.read_resource::<Loader>();
+		let texture_storage = &world.read_resource::<AssetStorage<Texture>>();
+		let stub_texture = || {
+			loader.load_from_data(TextureData::from(TextureBuilder::new()), (), texture_storage)
+		};
+		Self {
+			hero: stub_texture(),
+			slash_attack: stub_texture(),
+			thrust_attack: stub_texture(),
+			terrain: stub_texture(),
+			hearts: stub_texture(),
+			arrow_attack: stub_texture(),
+			shield: stub_texture(),
+			items: stub_texture(),
 		}
 	}
 }
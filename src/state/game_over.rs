@@ -0,0 +1,61 @@
+use crate::state::{
+	main_menu::load_most_recent_save,
+	menu::Menu,
+	playing::{handle_window_event, respawn_hero},
+	Playing,
+};
+
+use amethyst::prelude::*;
+
+/// Entries of the game over menu, in display order.
+const RESPAWN: usize = 0;
+const LOAD_SAVE: usize = 1;
+
+/// Shown on top of the gameplay state after the hero dies.
+pub struct GameOver {
+	menu: Option<Menu>,
+}
+
+impl GameOver {
+	pub fn new() -> Self {
+		Self { menu: None }
+	}
+}
+
+impl SimpleState for GameOver {
+	fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+		self.menu = Some(Menu::new(data.world, "Game Over", &["Respawn", "Load Save"]));
+	}
+
+	fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+		if let Some(menu) = self.menu.take() {
+			menu.delete(data.world);
+		}
+	}
+
+	fn handle_event(&mut self, data: StateData<'_, GameData<'_, '_>>, event: StateEvent) -> SimpleTrans {
+		handle_window_event(data.world, &event)
+	}
+
+	fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+		let world = &mut *data.world;
+		let chosen = match self.menu.as_mut() {
+			Some(menu) => menu.update(world),
+			None => None,
+		};
+		match chosen {
+			Some(RESPAWN) => {
+				respawn_hero(world);
+				Trans::Pop
+			},
+			Some(LOAD_SAVE) => {
+				// Replace the current game with the saved one, or stay on this menu if it couldn't be loaded.
+				match load_most_recent_save(world) {
+					Some(save) => Trans::NewStack(vec!(Box::new(Playing::from_save(save)))),
+					None => Trans::None,
+				}
+			},
+			_ => Trans::None,
+		}
+	}
+}
@@ -0,0 +1,71 @@
+use crate::{
+	config::GameplayConfig,
+	resource::SaveData,
+	state::{menu::Menu, playing::handle_window_event, Options, Playing},
+};
+
+use amethyst::prelude::*;
+
+/// Entries of the main menu, in display order.
+const NEW_GAME: usize = 0;
+const CONTINUE: usize = 1;
+const OPTIONS: usize = 2;
+const QUIT: usize = 3;
+
+/// The title screen, shown when the game starts.
+pub struct MainMenu {
+	menu: Option<Menu>,
+}
+
+impl MainMenu {
+	pub fn new() -> Self {
+		Self { menu: None }
+	}
+}
+
+impl SimpleState for MainMenu {
+	fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+		self.menu = Some(Menu::new(data.world, "Adventure", &["New Game", "Continue", "Options", "Quit"]));
+	}
+
+	fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+		if let Some(menu) = self.menu.take() {
+			menu.delete(data.world);
+		}
+	}
+
+	fn handle_event(&mut self, data: StateData<'_, GameData<'_, '_>>, event: StateEvent) -> SimpleTrans {
+		handle_window_event(data.world, &event)
+	}
+
+	fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+		let chosen = match self.menu.as_mut() {
+			Some(menu) => menu.update(data.world),
+			None => None,
+		};
+		match chosen {
+			Some(NEW_GAME) => Trans::Switch(Box::new(Playing::new())),
+			Some(CONTINUE) => match load_most_recent_save(data.world) {
+				Some(save) => Trans::Switch(Box::new(Playing::from_save(save))),
+				// Stay on this menu if there is nothing to continue.
+				None => Trans::None,
+			},
+			Some(OPTIONS) => Trans::Push(Box::new(Options::new())),
+			Some(QUIT) => Trans::Quit,
+			_ => Trans::None,
+		}
+	}
+}
+
+/// Loads the game saved at the configured save path, logging why if it can't be loaded.
+pub fn load_most_recent_save(world: &World) -> Option<SaveData> {
+	match world.try_fetch::<GameplayConfig>().and_then(|config| config.save_path.clone()) {
+		Some(save_path) => SaveData::load(&save_path).map_err(|error| {
+			log::error!("Could not load game from {}: {}", save_path.display(), error);
+		}).ok(),
+		None => {
+			log::error!("Could not load game: saving is disabled");
+			None
+		},
+	}
+}
@@ -0,0 +1,127 @@
+use crate::{
+	input_bindings::{Actions, InputBindings},
+	resource::ActionState,
+};
+
+use amethyst::{
+	assets::{AssetStorage, Loader},
+	ecs::Entity,
+	input::InputHandler,
+	prelude::*,
+	ui::{get_default_font, Anchor, FontAsset, LineMode, UiText, UiTransform},
+};
+
+/// The color of the title and of unselected entries.
+const TEXT_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+/// The color of the selected entry.
+const SELECTED_COLOR: [f32; 4] = [1.0, 0.85, 0.2, 1.0];
+const TITLE_FONT_SIZE: f32 = 48.0;
+const ENTRY_FONT_SIZE: f32 = 28.0;
+/// The vertical distance between the centers of consecutive lines.
+const LINE_SPACING: f32 = 50.0;
+const TEXT_WIDTH: f32 = 400.0;
+
+/// A vertical list of text entries under a title, centered on the screen. The player moves the selection with
+/// Up/Down and chooses the selected entry with Primary.
+pub struct Menu {
+	title_id: Entity,
+	entry_ids: Vec<Entity>,
+	selected: usize,
+	actions: ActionState,
+	actions_last_frame: ActionState,
+}
+
+impl Menu {
+	/// Creates the menu's UI entities in `world`, with the first entry selected.
+	pub fn new(world: &mut World, title: &str, entries: &[&str]) -> Self {
+		let font = get_default_font(
+			&world.read_resource::<Loader>(),
+			&world.read_resource::<AssetStorage<FontAsset>>(),
+		);
+		let create_text = |world: &mut World, text: &str, font_size: f32, y: f32| {
+			world
+				.create_entity()
+				.with(UiTransform::new(
+					text.to_string(),
+					Anchor::Middle,
+					Anchor::Middle,
+					0.0,
+					y,
+					1.0,
+					TEXT_WIDTH,
+					LINE_SPACING,
+				))
+				.with(UiText::new(
+					font.clone(),
+					text.to_string(),
+					TEXT_COLOR,
+					font_size,
+					LineMode::Single,
+					Anchor::Middle,
+				))
+				.build()
+		};
+		// Center the title and entries vertically as a group.
+		let top = 0.5 * LINE_SPACING * entries.len() as f32;
+		let title_id = create_text(world, title, TITLE_FONT_SIZE, top);
+		let entry_ids = entries.iter().enumerate().map(|(i, entry)| {
+			create_text(world, entry, ENTRY_FONT_SIZE, top - LINE_SPACING * (i + 1) as f32)
+		}).collect();
+		// Treat actions that are already held as old, so that e.g. the attack that opened the menu doesn't choose
+		// an entry.
+		let actions = ActionState::from_input(&world.read_resource::<InputHandler<InputBindings>>());
+		let menu = Self {
+			title_id,
+			entry_ids,
+			selected: 0,
+			actions,
+			actions_last_frame: actions,
+		};
+		menu.highlight_selected(world);
+		menu
+	}
+
+	/// Handles this frame's input. Returns the index of the chosen entry, if one was chosen.
+	pub fn update(&mut self, world: &mut World) -> Option<usize> {
+		self.actions_last_frame = self.actions;
+		self.actions = ActionState::from_input(&world.read_resource::<InputHandler<InputBindings>>());
+		let mut chosen = None;
+		if self.pressed(Actions::Up) {
+			self.selected = (self.selected + self.entry_ids.len() - 1) % self.entry_ids.len();
+		} else if self.pressed(Actions::Down) {
+			self.selected = (self.selected + 1) % self.entry_ids.len();
+		} else if self.pressed(Actions::Primary) {
+			chosen = Some(self.selected);
+		}
+		self.highlight_selected(world);
+		chosen
+	}
+
+	/// Whether `action` was pressed as of the last call to `update`.
+	pub fn pressed(&self, action: Actions) -> bool {
+		self.actions.is_down(action) && !self.actions_last_frame.is_down(action)
+	}
+
+	/// Changes the text of the entry at `entry_idx`.
+	pub fn set_entry_text(&self, world: &World, entry_idx: usize, text: &str) {
+		world.write_storage::<UiText>().get_mut(self.entry_ids[entry_idx]).unwrap().text = text.to_string();
+	}
+
+	/// Deletes the menu's UI entities.
+	pub fn delete(&self, world: &mut World) {
+		world.delete_entity(self.title_id).unwrap();
+		world.delete_entities(&self.entry_ids).unwrap();
+	}
+
+	/// Colors the selected entry differently from the rest.
+	fn highlight_selected(&self, world: &World) {
+		let mut sto_ui_text = world.write_storage::<UiText>();
+		for (i, entry_id) in self.entry_ids.iter().enumerate() {
+			sto_ui_text.get_mut(*entry_id).unwrap().color = if i == self.selected {
+				SELECTED_COLOR
+			} else {
+				TEXT_COLOR
+			};
+		}
+	}
+}
@@ -1,3 +1,12 @@
+mod game_over;
+mod main_menu;
+mod menu;
+mod options;
+mod paused;
 mod playing;
 
-pub use playing::Playing;
+pub use game_over::GameOver;
+pub use main_menu::MainMenu;
+pub use options::Options;
+pub use paused::Paused;
+pub use playing::{build_dispatcher, start_game, take_exits, Playing};
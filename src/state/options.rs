@@ -0,0 +1,88 @@
+use crate::{
+	input_bindings::Actions,
+	resource::DisplaySettings,
+	state::{menu::Menu, playing::handle_window_event},
+};
+
+use amethyst::{
+	prelude::*,
+	window::Window,
+};
+
+/// Entries of the options menu, in display order.
+const FULLSCREEN: usize = 0;
+const BACK: usize = 1;
+
+/// A menu of settings, shown on top of whichever state opened it.
+pub struct Options {
+	menu: Option<Menu>,
+}
+
+impl Options {
+	pub fn new() -> Self {
+		Self { menu: None }
+	}
+}
+
+impl SimpleState for Options {
+	fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+		let menu = Menu::new(data.world, "Options", &["", "Back"]);
+		menu.set_entry_text(data.world, FULLSCREEN, fullscreen_text(data.world));
+		self.menu = Some(menu);
+	}
+
+	fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+		if let Some(menu) = self.menu.take() {
+			menu.delete(data.world);
+		}
+	}
+
+	fn handle_event(&mut self, data: StateData<'_, GameData<'_, '_>>, event: StateEvent) -> SimpleTrans {
+		handle_window_event(data.world, &event)
+	}
+
+	fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+		let menu = match self.menu.as_mut() {
+			Some(menu) => menu,
+			None => return Trans::None,
+		};
+		let chosen = menu.update(data.world);
+		if menu.pressed(Actions::Pause) {
+			return Trans::Pop;
+		}
+		match chosen {
+			Some(FULLSCREEN) => {
+				// Toggle fullscreen on the window's current monitor.
+				let mut display_settings = display_settings(data.world);
+				display_settings.fullscreen = !display_settings.fullscreen;
+				{
+					let window = data.world.read_resource::<Window>();
+					if display_settings.fullscreen {
+						window.set_fullscreen(Some(window.get_current_monitor()));
+					} else {
+						window.set_fullscreen(None);
+					}
+				}
+				data.world.insert(display_settings);
+				menu.set_entry_text(data.world, FULLSCREEN, fullscreen_text(data.world));
+				Trans::None
+			},
+			Some(BACK) => Trans::Pop,
+			_ => Trans::None,
+		}
+	}
+}
+
+/// The current display settings, or the defaults if they haven't been changed yet.
+fn display_settings(world: &World) -> DisplaySettings {
+	world.try_fetch::<DisplaySettings>().map_or_else(DisplaySettings::default, |settings| *settings)
+}
+
+/// The text of the fullscreen entry, reflecting the current setting.
+fn fullscreen_text(world: &World) -> &'static str {
+	if display_settings(world).fullscreen {
+		"Fullscreen: On"
+	} else {
+		"Fullscreen: Off"
+	}
+}
@@ -0,0 +1,57 @@
+use crate::{
+	input_bindings::Actions,
+	state::{menu::Menu, playing::handle_window_event, Options},
+};
+
+use amethyst::prelude::*;
+
+/// Entries of the pause menu, in display order.
+const RESUME: usize = 0;
+const OPTIONS: usize = 1;
+const QUIT: usize = 2;
+
+/// Shown on top of the gameplay state while the game is paused. Gameplay systems don't run, but the world is
+/// still rendered underneath the menu.
+pub struct Paused {
+	menu: Option<Menu>,
+}
+
+impl Paused {
+	pub fn new() -> Self {
+		Self { menu: None }
+	}
+}
+
+impl SimpleState for Paused {
+	fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+		self.menu = Some(Menu::new(data.world, "Paused", &["Resume", "Options", "Quit"]));
+	}
+
+	fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+		if let Some(menu) = self.menu.take() {
+			menu.delete(data.world);
+		}
+	}
+
+	fn handle_event(&mut self, data: StateData<'_, GameData<'_, '_>>, event: StateEvent) -> SimpleTrans {
+		handle_window_event(data.world, &event)
+	}
+
+	fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+		let menu = match self.menu.as_mut() {
+			Some(menu) => menu,
+			None => return Trans::None,
+		};
+		let chosen = menu.update(data.world);
+		// Pressing pause again also resumes.
+		if menu.pressed(Actions::Pause) {
+			return Trans::Pop;
+		}
+		match chosen {
+			Some(RESUME) => Trans::Pop,
+			Some(OPTIONS) => Trans::Push(Box::new(Options::new())),
+			Some(QUIT) => Trans::Quit,
+			_ => Trans::None,
+		}
+	}
+}
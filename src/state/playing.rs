@@ -1,38 +1,47 @@
 use crate::{
 	component::{
-		animation::{DirectionalAnimation, DirectionalFrame, SimpleAnimation, SimpleFrame},
-		behavior::{ShootArrows, Wander},
-		collider::RectangleCollider,
 		Direction,
-		Enemy,
-		Faction,
 		Health,
-		Heart,
 		Hero,
 		HeroState,
+		Invulnerable,
+		ItemSlot,
+		KnockedBack,
 		Position,
-		removal::{TiedToEntity, TiedToRegion},
-		Shield,
+		removal::TiedToRegion,
+		SpawnIndex,
 		Terrain,
 		TileCoords,
 		Velocity,
 	},
+	config::{GameplayConfig, RespawnPolicies},
 	constants::*,
+	input_bindings::{Actions, InputBindings},
 	resource::{
+		ActionState,
 		Camera,
 		CurrentRegion,
+		EnemyArchetypes,
+		GameRng,
 		Hud,
+		LastEntrance,
+		Prefabs,
 		Region,
 		RegionData,
+		RegionStates,
+		Replay,
+		SaveData,
 		SpriteSheets,
 		Textures,
 	},
+	state::{GameOver, Paused},
 	system::*,
 };
 
 use amethyst::{
 	core::{ArcThreadPool, transform::Transform},
-	ecs::{Dispatcher, DispatcherBuilder, Entity, Join},
+	ecs::{Dispatcher, DispatcherBuilder, Entity, Join, System},
+	input::InputHandler,
 	prelude::*,
 	renderer::{Camera as AmethystCamera},
 	utils::removal::{exec_removal, Removal},
@@ -43,19 +52,57 @@ use amethyst::{
 		WindowEvent,
 	},
 };
-use rand::Rng;
 use ron::de::from_reader;
 
-use std::fs::File;
+use std::{fs::File, path::PathBuf};
 
 /// The main gameplay state.
 pub struct Playing<'a, 'b> {
 	dispatcher: Option<Dispatcher<'a, 'b>>,
+	mode: Mode,
+	/// Whether the pause action was held down during the last update, to detect new presses.
+	pause_down_last_update: bool,
+	/// The saved game to resume from when the state starts, if not starting a new game.
+	save: Option<SaveData>,
+}
+
+/// Where the hero's input comes from and what happens to it.
+enum Mode {
+	/// Input comes from the player.
+	Live,
+	/// Input comes from the player and is recorded, to be saved to `path` when the state stops.
+	Recording { path: PathBuf, replay: Option<Replay> },
+	/// Input comes from `replay`, and the world is checked against the recording each frame.
+	Replaying { replay: Replay, frame: usize, desynced: bool },
 }
 
 impl<'a, 'b> Playing<'a, 'b> {
 	pub fn new() -> Self {
-		Self { dispatcher: None }
+		Self::with_mode(Mode::Live, None)
+	}
+
+	/// Resumes the game saved in `save`.
+	pub fn from_save(save: SaveData) -> Self {
+		Self::with_mode(Mode::Live, Some(save))
+	}
+
+	/// Plays normally while recording a replay, which is saved to `path` when the state stops.
+	pub fn recording(path: PathBuf) -> Self {
+		Self::with_mode(Mode::Recording { path, replay: None }, None)
+	}
+
+	/// Plays back `replay` instead of reading player input, logging an error if the world desyncs.
+	pub fn replaying(replay: Replay) -> Self {
+		Self::with_mode(Mode::Replaying { replay, frame: 0, desynced: false }, None)
+	}
+
+	fn with_mode(mode: Mode, save: Option<SaveData>) -> Self {
+		Self {
+			dispatcher: None,
+			mode,
+			pause_down_last_update: false,
+			save,
+		}
 	}
 }
 
@@ -64,132 +111,73 @@ impl<'a, 'b> SimpleState for Playing<'a, 'b> {
 		let world = data.world;
 
 		// Set up dispatcher for this state.
-		let mut dispatcher = DispatcherBuilder::new()
-			.with_pool((*world.read_resource::<ArcThreadPool>()).clone())
-			.with(HeroControl::new(), "hero_control", &[])
-			.with(EnemyControl, "enemy_control", &[])
-			.with(Knockback, "knockback", &[])
-			.with(Invulnerability, "invulnerability", &[])
-			.with_barrier()
-			.with(Motion, "motion", &[])
-			.with_barrier()
-			.with(StaticCollisionDetection, "static_collision_detection", &[])
-			.with_barrier()
-			.with(DynamicCollisionDetection, "dynamic_collision_detection", &[])
-			.with_barrier()
-			.with(Death, "death", &[])
-			.with_barrier()
-			.with(AttackUpdates, "attack_updates", &[])
-			.with_barrier()
-			.with(Animation, "animation", &[])
-			.with(CameraControl, "camera_control", &[])
-			.with(HudUpdates, "hud_updates", &[])
-			.build();
-		dispatcher.setup(world);
-		self.dispatcher = Some(dispatcher);
-
-		// Register required components.
-		world.register::<Terrain>();
-		world.register::<Velocity>();
+		self.dispatcher = Some(match self.mode {
+			Mode::Live | Mode::Recording { .. } => build_dispatcher(world, PlayerInput),
+			Mode::Replaying { .. } => build_dispatcher(world, ScriptedInput),
+		});
 
 		// Load textures and sprite sheets.
 		world.insert(Textures::new(&world));
 		world.insert(SpriteSheets::new(&world));
 
-		// Create a current region manager.
-		world.insert(CurrentRegion::new());
-
-		// Create hero (player character) and give it a shield.
-		let hero_position = Position { x: TILE_SIZE * 30.0, y: -TILE_SIZE * 30.0 };
-		let hero_direction = Direction::Down;
-		let hero_collider = RectangleCollider {
-			half_width: 0.5 * TILE_SIZE,
-			half_height: 0.5 * TILE_SIZE,
-		};
-		let hero_sprite_sheet = world.read_resource::<SpriteSheets>().hero.clone();
-		// Create hero's shield components.
-		let shield_position = Shield::compute_position(
-			&hero_position,
-			&hero_direction,
-			&hero_collider,
-		);
-		let shield_collider = Shield::compute_collider(&hero_direction);
-		// Create hero.
-		let hero_id = world
-			.create_entity()
-			.with(Hero { state: HeroState::FreelyMoving })
-			.with(Faction::Ally)
-			.with(Health::new(HERO_BASE_HEALTH))
-			.with(hero_position)
-			.with(Velocity::default())
-			.with(hero_direction)
-			.with(hero_collider)
-			.with(DirectionalAnimation::new(
-				hero_sprite_sheet,
-				vec!(DirectionalFrame {
-					up: 0,
-					down: 1,
-					left: 2,
-					right: 3,
-					duration: None,
-				}),
-			))
-			.build();
-		// Create hero's shield.
-		let shield_sprite_sheet = world.read_resource::<SpriteSheets>().shield.clone();
-		world
-			.create_entity()
-			.with(Shield::new(hero_id))
-			.with(Removal::new(TiedToEntity(hero_id)))
-			.with(shield_position)
-			.with(hero_direction)
-			.with(shield_collider)
-			.with(DirectionalAnimation::new(
-				shield_sprite_sheet,
-				vec!(DirectionalFrame {
-					up: 0,
-					down: 1,
-					left: 2,
-					right: 3,
-					duration: None,
-				}),
-			))
-			.build();
-
-		// Load starting region.
-		load_region("test.ron", world);
-
-		// Move hero to the region's second entrance.
-		place_at_entrance(hero_id, 1, world);
-
-		// Create and insert HUD.
-		world.insert(Hud::new());
-
-		add_camera(world);
-	}
-
-	fn handle_event(&mut self, data: StateData<'_, GameData<'_, '_>>, event: StateEvent) -> SimpleTrans {
-		if let StateEvent::Window(Event::WindowEvent { event, .. }) = &event {
-			match event {
-				WindowEvent::Resized(LogicalSize { width, height }) => {
-					let camera = data.world.fetch::<Camera>();
-					let mut sto_amethyst_camera = data.world.write_storage::<AmethystCamera>();
-					let amethyst_camera = sto_amethyst_camera.get_mut(camera.id).unwrap();
-					// Ensure the width and height are even. This avoids minor texture stretching.
-					let width = *width as u32;
-					let height = *height as u32;
-					let width = width + (width & 1);
-					let height = height + (height & 1);
-					*amethyst_camera = AmethystCamera::standard_2d(width as f32, height as f32);
+		match &mut self.mode {
+			Mode::Live => match self.save.take() {
+				Some(save) => {
+					resume_game(world, &save);
 				},
-				WindowEvent::CloseRequested => {
-					return Trans::Quit;
+				None => {
+					let (region, entrance_idx) = starting_location(world);
+					start_game(world, &region, entrance_idx);
 				},
-				// Ignore other events.
-				_ => {},
+			},
+			Mode::Recording { replay, .. } => {
+				let (region, entrance_idx) = starting_location(world);
+				let seed = world.read_resource::<GameRng>().seed();
+				*replay = Some(Replay::new(seed, &region, entrance_idx, respawn_policies(world)));
+				start_game(world, &region, entrance_idx);
+			},
+			Mode::Replaying { replay, .. } => {
+				// Reproduce the recorded session's randomness, input and respawn policies.
+				world.insert(GameRng::new(replay.seed));
+				world.insert(replay.input_script());
+				let mut config = world.try_fetch::<GameplayConfig>()
+					.map_or_else(GameplayConfig::default, |config| config.clone());
+				config.respawn = replay.respawn;
+				world.insert(config);
+				start_game(world, &replay.region, replay.entrance_idx);
+			},
+		}
+	}
+
+	fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+		if let Mode::Live = self.mode {
+			autosave(data.world);
+		}
+		if let Mode::Recording { path, replay: Some(replay) } = &self.mode {
+			match replay.save(path) {
+				Ok(()) => log::info!("Saved replay to {}", path.display()),
+				Err(error) => log::error!("Could not save replay to {}: {}", path.display(), error),
 			}
 		}
-		Trans::None
+		// Clear the world so that another game can be started.
+		data.world.delete_all();
+	}
+
+	fn handle_event(&mut self, data: StateData<'_, GameData<'_, '_>>, event: StateEvent) -> SimpleTrans {
+		handle_window_event(data.world, &event)
+	}
+
+	fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+		// Pause on a new press of the pause action.
+		let actions = ActionState::from_input(&data.world.read_resource::<InputHandler<InputBindings>>());
+		let pause_down = actions.is_down(Actions::Pause);
+		let pause_pressed = pause_down && !self.pause_down_last_update;
+		self.pause_down_last_update = pause_down;
+		if pause_pressed {
+			Trans::Push(Box::new(Paused::new()))
+		} else {
+			Trans::None
+		}
 	}
 
 	fn fixed_update(&mut self, data: StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
@@ -198,39 +186,225 @@ impl<'a, 'b> SimpleState for Playing<'a, 'b> {
 		if let Some(dispatcher) = self.dispatcher.as_mut() {
 			dispatcher.dispatch(world);
 		}
-		// See if a hero is on an exit.
-		let exits = world.read_resource::<CurrentRegion>().get().exits().clone();
-		let mut hero_id_exit = None;
-		for (hero_id, _hero, hero_position) in (
-			&*world.entities(),
-			&world.read_storage::<Hero>(),
-			&world.read_storage::<Position>(),
-		).join() {
-			let tile_coords: Option<TileCoords> = (*hero_position).into();
-			if let Some(tile_coords) = tile_coords {
-				for exit in &exits {
-					if exit.location == tile_coords {
-						hero_id_exit = Some((hero_id, exit.clone()));
-						break;
+		if take_exits(world) {
+			if let Mode::Live = self.mode {
+				autosave(world);
+			}
+		}
+		if let Mode::Recording { .. } | Mode::Replaying { .. } = self.mode {
+			// Apply pending deletions so that checksums match those computed by the headless simulation.
+			world.maintain();
+		}
+		match &mut self.mode {
+			Mode::Live => {},
+			Mode::Recording { replay, .. } => {
+				if let Some(replay) = replay {
+					replay.record_frame(world);
+				}
+			},
+			Mode::Replaying { replay, frame, desynced } => {
+				// Only report the first desync, since every later frame will most likely mismatch too.
+				if !*desynced {
+					if let Err(desync) = replay.verify_frame(*frame, world) {
+						log::error!("{}", desync);
+						*desynced = true;
+					} else if *frame + 1 == replay.frames.len() {
+						log::info!("Replay of {} frames verified", replay.frames.len());
 					}
 				}
+				*frame += 1;
+			},
+		}
+		// End the game once the hero has finished dying.
+		let hero_dead = world.read_storage::<Hero>().join().any(|hero| hero.state == HeroState::Dead);
+		if hero_dead {
+			Trans::Push(Box::new(GameOver::new()))
+		} else {
+			Trans::None
+		}
+	}
+}
+
+/// Builds the dispatcher that runs the gameplay systems each fixed frame and sets up their resources.
+/// `input_system` - Populates the `ActionState` resource at the start of each frame.
+pub fn build_dispatcher<'a, 'b, I>(world: &mut World, input_system: I) -> Dispatcher<'a, 'b>
+where
+	I: for<'c> System<'c> + Send + 'a,
+{
+	let mut dispatcher = DispatcherBuilder::new()
+		.with_pool((*world.read_resource::<ArcThreadPool>()).clone())
+		.with(input_system, "input", &[])
+		.with_barrier()
+		.with(HeroControl::new(), "hero_control", &[])
+		.with(EnemyControl::new(), "enemy_control", &[])
+		.with(ItemUpdates, "item_updates", &[])
+		.with(ProjectileUpdates, "projectile_updates", &[])
+		.with(Knockback, "knockback", &[])
+		.with(Invulnerability, "invulnerability", &[])
+		.with_barrier()
+		.with(Motion, "motion", &[])
+		.with_barrier()
+		.with(StaticCollisionDetection, "static_collision_detection", &[])
+		.with_barrier()
+		.with(DynamicCollisionDetection, "dynamic_collision_detection", &[])
+		.with_barrier()
+		.with(Death, "death", &[])
+		.with_barrier()
+		.with(AttackUpdates, "attack_updates", &[])
+		.with_barrier()
+		.with(Animation, "animation", &[])
+		.with(TintUpdates, "tint_updates", &[])
+		.with(CameraControl, "camera_control", &[])
+		.with(HudUpdates, "hud_updates", &[])
+		.build();
+	dispatcher.setup(world);
+	dispatcher
+}
+
+/// Populates `world` with the hero, the HUD, and the camera, and places the hero at `entrance_idx` in `region`.
+/// Expects `Textures`, `SpriteSheets`, and `GameRng` resources to be present already. Returns the hero's entity ID.
+pub fn start_game(world: &mut World, region: &str, entrance_idx: usize) -> Entity {
+	let hero_id = set_up_world(world);
+
+	// Load starting region.
+	world.insert(RegionStates::default());
+	load_region(region, world);
+
+	// Move hero to the starting entrance.
+	place_at_entrance(hero_id, entrance_idx, world);
+
+	hero_id
+}
+
+/// Populates `world` with the hero, the HUD, and the camera, and restores the region and hero from `save`.
+/// Expects the same resources as `start_game`. Returns the hero's entity ID.
+pub fn resume_game(world: &mut World, save: &SaveData) -> Entity {
+	let hero_id = set_up_world(world);
+
+	// Load the saved region. Resuming a game counts as resting.
+	let mut region_states = save.region_states.clone();
+	region_states.rest(&respawn_policies(world));
+	world.insert(region_states);
+	load_region(&save.region, world);
+	world.insert(LastEntrance { idx: save.entrance_idx });
+
+	// Restore the hero.
+	*world.write_storage::<Position>().get_mut(hero_id).unwrap() = save.hero_position;
+	*world.write_storage::<Direction>().get_mut(hero_id).unwrap() = save.hero_direction;
+	*world.write_storage::<Health>().get_mut(hero_id).unwrap() = save.hero_health;
+	world.write_storage::<ItemSlot>().get_mut(hero_id).unwrap().item = save.hero_item;
+
+	hero_id
+}
+
+/// Creates the current region manager, the hero and its shield, the HUD, and the camera. Returns the hero's entity ID.
+fn set_up_world(world: &mut World) -> Entity {
+	// Register required components.
+	world.register::<Terrain>();
+	world.register::<Velocity>();
+	world.register::<AmethystCamera>();
+
+	// Create a current region manager.
+	world.insert(CurrentRegion::new());
+
+	// Start with no enemy archetypes or prefabs loaded, so that edited files take effect in a new game.
+	world.insert(EnemyArchetypes::default());
+	world.insert(Prefabs::default());
+
+	// Create hero (player character) and its shield.
+	let hero_position = Position { x: TILE_SIZE * 30.0, y: -TILE_SIZE * 30.0 };
+	let hero_id = Prefabs::instantiate(world, "hero", hero_position, &[]);
+
+	// Create and insert HUD.
+	world.insert(Hud::new());
+
+	add_camera(world);
+
+	hero_id
+}
+
+/// Moves any hero standing on an exit of the current region into the exit's target region. Returns whether an exit
+/// was taken.
+pub fn take_exits(world: &mut World) -> bool {
+	// See if a hero is on an exit.
+	let exits = world.read_resource::<CurrentRegion>().get().exits().clone();
+	let mut hero_id_exit = None;
+	for (hero_id, _hero, hero_health, hero_position) in (
+		&*world.entities(),
+		&world.read_storage::<Hero>(),
+		&world.read_storage::<Health>(),
+		&world.read_storage::<Position>(),
+	).join() {
+		// Dead heroes can't leave.
+		if hero_health.current() == 0 {
+			continue;
+		}
+		let tile_coords: Option<TileCoords> = (*hero_position).into();
+		if let Some(tile_coords) = tile_coords {
+			for exit in &exits {
+				if exit.location == tile_coords {
+					hero_id_exit = Some((hero_id, exit.clone()));
+					break;
+				}
 			}
-			if hero_id_exit.is_some() {
-				break;
-			}
 		}
-		// If so, take the exit.
-		if let Some((hero_id, exit)) = hero_id_exit {
-			// Remove all entities associated with the current region.
-			exec_removal(&*world.entities(), &world.read_storage::<Removal<TiedToRegion>>(), TiedToRegion);
-			// Load the target region.
-			load_region(&exit.target_region, world);
-			// Place the hero at the target entrance.
-			place_at_entrance(hero_id, exit.target_entrance_idx, world);
-			// Reset the hero's state.
-			world.write_storage::<Hero>().get_mut(hero_id).unwrap().state = HeroState::FreelyMoving;
+		if hero_id_exit.is_some() {
+			break;
+		}
+	}
+	// If so, take the exit.
+	if let Some((hero_id, exit)) = hero_id_exit {
+		// Remove all entities associated with the current region.
+		exec_removal(&*world.entities(), &world.read_storage::<Removal<TiedToRegion>>(), TiedToRegion);
+		// Load the target region.
+		load_region(&exit.target_region, world);
+		// Place the hero at the target entrance.
+		place_at_entrance(hero_id, exit.target_entrance_idx, world);
+		// Reset the hero's state.
+		world.write_storage::<Hero>().get_mut(hero_id).unwrap().state = HeroState::FreelyMoving;
+		true
+	} else {
+		false
+	}
+}
+
+/// Brings the dead hero back to life at the last entrance they used. The current region is reloaded, and the hero
+/// rests, respawning region entities according to the respawn policies.
+pub fn respawn_hero(world: &mut World) {
+	let hero_id = match (&*world.entities(), &world.read_storage::<Hero>()).join().next() {
+		Some((hero_id, _hero)) => hero_id,
+		None => return,
+	};
+	// Reload the current region after resting.
+	exec_removal(&*world.entities(), &world.read_storage::<Removal<TiedToRegion>>(), TiedToRegion);
+	let policies = respawn_policies(world);
+	world.write_resource::<RegionStates>().rest(&policies);
+	let filename = world.read_resource::<CurrentRegion>().get().filename().to_string();
+	load_region(&filename, world);
+	// Place the hero at the last entrance.
+	let entrance_idx = world.read_resource::<LastEntrance>().idx;
+	place_at_entrance(hero_id, entrance_idx, world);
+	// Restore the hero.
+	let max_health = world.read_storage::<Health>().get(hero_id).unwrap().max();
+	*world.write_storage::<Health>().get_mut(hero_id).unwrap() = Health::new(max_health);
+	world.write_storage::<Hero>().get_mut(hero_id).unwrap().state = HeroState::FreelyMoving;
+	*world.write_storage::<Velocity>().get_mut(hero_id).unwrap() = Velocity::default();
+	world.write_storage::<KnockedBack>().remove(hero_id);
+	world.write_storage::<Invulnerable>().remove(hero_id);
+}
+
+/// Saves the game to the configured save path, if there is one.
+fn autosave(world: &World) {
+	let save_path = match world.try_fetch::<GameplayConfig>().and_then(|config| config.save_path.clone()) {
+		Some(save_path) => save_path,
+		None => return,
+	};
+	// There is nothing to save if the hero is dead.
+	if let Some(save) = SaveData::from_world(world) {
+		match save.save(&save_path) {
+			Ok(()) => log::info!("Saved game to {}", save_path.display()),
+			Err(error) => log::error!("Could not save game to {}: {}", save_path.display(), error),
 		}
-		Trans::None
 	}
 }
 
@@ -247,15 +421,22 @@ fn add_camera(world: &mut World) {
 	world.insert(Camera { id: camera_id });
 }
 
-/// Loads a region from `filename` within the `assets/regions` directory.
+/// Loads a region from `filename` within the `assets/regions` directory and makes it current in the `RegionStates`
+/// resource. Entities the hero has removed and that are not yet due to respawn are not spawned.
 fn load_region(filename: &str, world: &mut World) {
 	// Load region data from file.
 	let path = format!("assets/regions/{}", filename);
 	let file = File::open(&path).expect("Could not open region file");
 	let region_data: RegionData = from_reader(file).expect("Error in region file");
+	let enemy_overrides = region_data.enemies.iter().flat_map(|enemy_data| &enemy_data.overrides);
+	let prefab_overrides = region_data.prefabs.iter().flat_map(|instance| &instance.overrides);
+	for component_override in enemy_overrides.chain(prefab_overrides) {
+		component_override.validate().unwrap_or_else(|error| panic!("Error in region file {}: {}", filename, error));
+	}
 
 	// Load the region itself.
 	let region = Region::new(
+		filename,
 		region_data.col_count,
 		region_data.terrain,
 		region_data.entrances,
@@ -264,66 +445,75 @@ fn load_region(filename: &str, world: &mut World) {
 	);
 	world.write_resource::<CurrentRegion>().set(region);
 
+	let policies = respawn_policies(world);
+	let region_changes = world.write_resource::<RegionStates>().enter(filename, &policies).clone();
+
 	// Generate enemies.
-	for enemy_data in region_data.enemies {
-		let enemy_position: Position = enemy_data.location.into();
-		let enemy_collider = RectangleCollider {
-			half_width: 0.5 * TILE_SIZE,
-			half_height: 0.5 * TILE_SIZE,
-		};
-		let enemy_sprite_sheet = world.read_resource::<SpriteSheets>().enemy.clone();
-		world
-			.create_entity()
-			.with(Enemy)
-			.with(Removal::new(TiedToRegion))
-			.with(Faction::Enemy)
-			.with(Health::new(ENEMY_BASE_HEALTH))
-			.with(Wander { direction: rand::thread_rng().gen() })
-			.with(ShootArrows::new())
-			.with(enemy_position)
-			.with(Velocity::default())
-			.with(Direction::Down)
-			.with(enemy_collider)
-			.with(DirectionalAnimation::new(
-				enemy_sprite_sheet,
-				vec!(DirectionalFrame {
-					up: 0,
-					down: 1,
-					left: 2,
-					right: 3,
-					duration: None,
-				}),
-			))
-			.build();
+	for (enemy_idx, enemy_data) in region_data.enemies.into_iter().enumerate() {
+		if region_changes.killed_enemies.contains_key(&enemy_idx) {
+			continue;
+		}
+		let prefab = world.write_resource::<EnemyArchetypes>().get(&enemy_data.archetype).prefab();
+		let id = Prefabs::build(world, prefab, enemy_data.location.into(), &enemy_data.overrides);
+		world.write_storage::<SpawnIndex>().insert(id, SpawnIndex(enemy_idx)).unwrap();
 	}
 
-	// Generate hearts.
-	for heart_location in region_data.heart_locations {
-		let heart_position: Position = heart_location.into();
-		let heart_collider = RectangleCollider {
-			half_width: 0.5 * HEART_WIDTH,
-			half_height: 0.5 * HEART_HEIGHT,
-		};
-		let heart_sprite_sheet = world.read_resource::<SpriteSheets>().hearts.clone();
-		world
-			.create_entity()
-			.with(Heart)
-			.with(Removal::new(TiedToRegion))
-			.with(heart_position)
-			.with(Direction::Down)
-			.with(heart_collider)
-			.with(SimpleAnimation::new(heart_sprite_sheet, vec!(
-				SimpleFrame {
-					sprite_number: 1,
-					duration: None,
-				},
-			)))
-			.build();
+	// Generate the region's other prefab instances, except hearts that have been picked up.
+	for (instance_idx, instance) in region_data.prefabs.into_iter().enumerate() {
+		if region_changes.collected_hearts.contains_key(&instance_idx) {
+			continue;
+		}
+		let id = Prefabs::instantiate(world, &instance.prefab, instance.location.into(), &instance.overrides);
+		world.write_storage::<SpawnIndex>().insert(id, SpawnIndex(instance_idx)).unwrap();
 	}
 }
 
-/// Places `entity` at the entrance of the current region at index `entrance_idx`.
+/// The configured starting region filename and entrance index for a new game, or the defaults if there is no
+/// configuration.
+fn starting_location(world: &World) -> (String, usize) {
+	match world.try_fetch::<GameplayConfig>() {
+		Some(config) => (config.starting_region.clone(), config.starting_entrance_idx),
+		None => (STARTING_REGION.to_string(), STARTING_ENTRANCE_IDX),
+	}
+}
+
+/// Keeps the camera's projection in sync with the window's size and quits when the window is closed. Shared by every
+/// state, since only the topmost state receives events.
+pub fn handle_window_event(world: &World, event: &StateEvent) -> SimpleTrans {
+	if let StateEvent::Window(Event::WindowEvent { event, .. }) = event {
+		match event {
+			WindowEvent::Resized(LogicalSize { width, height }) => {
+				// There's no camera until a game has started.
+				if let Some(camera) = world.try_fetch::<Camera>() {
+					let mut sto_amethyst_camera = world.write_storage::<AmethystCamera>();
+					let amethyst_camera = sto_amethyst_camera.get_mut(camera.id).unwrap();
+					// Ensure the width and height are even. This avoids minor texture stretching.
+					let width = *width as u32;
+					let height = *height as u32;
+					let width = width + (width & 1);
+					let height = height + (height & 1);
+					*amethyst_camera = AmethystCamera::standard_2d(width as f32, height as f32);
+				}
+			},
+			WindowEvent::CloseRequested => {
+				return Trans::Quit;
+			},
+			// Ignore other events.
+			_ => {},
+		}
+	}
+	Trans::None
+}
+
+/// The configured respawn policies, or the defaults if there is no configuration.
+fn respawn_policies(world: &World) -> RespawnPolicies {
+	world.try_fetch::<GameplayConfig>().map_or_else(RespawnPolicies::default, |config| config.respawn)
+}
+
+/// Places `entity` at the entrance of the current region at index `entrance_idx`, and records it as the last
+/// entrance used.
 fn place_at_entrance(entity: Entity, entrance_idx: usize, world: &mut World) {
+	world.insert(LastEntrance { idx: entrance_idx });
 	let entrance = world.read_resource::<CurrentRegion>().get().entrances()[entrance_idx];
 	let mut sto_position = world.write_storage::<Position>();
 	let position = sto_position.get_mut(entity).unwrap();
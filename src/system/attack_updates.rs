@@ -5,13 +5,15 @@ use crate::{
 		Position,
 		Shield,
 		SlashAttack,
+		SpinAttack,
 		ThrustAttack,
 	},
 };
 
 use amethyst::{
+	core::Hidden,
 	derive::SystemDesc,
-	ecs::{Entities, Join, System, SystemData, WriteStorage},
+	ecs::{Entities, Join, ReadStorage, System, SystemData, WriteStorage},
 };
 
 /// Keeps attacks synchronized with their sources.
@@ -23,22 +25,26 @@ impl<'a> System<'a> for AttackUpdates {
 		Entities<'a>,
 		WriteStorage<'a, SlashAttack>,
 		WriteStorage<'a, ThrustAttack>,
+		ReadStorage<'a, SpinAttack>,
 		WriteStorage<'a, Shield>,
 		WriteStorage<'a, Position>,
 		WriteStorage<'a, Direction>,
 		WriteStorage<'a, HalfDiskCollider>,
 		WriteStorage<'a, RectangleCollider>,
+		WriteStorage<'a, Hidden>,
 	);
 
 	fn run(&mut self, (
 		entities,
 		sto_slash_attack,
 		sto_thrust_attack,
-		sto_shield,
+		sto_spin_attack,
+		mut sto_shield,
 		mut sto_position,
 		mut sto_direction,
 		mut sto_disk_arc_collider,
 		mut sto_rectangle_collider,
+		mut sto_hidden,
 	): Self::SystemData) {
 		// Slash attack updates.
 		for (slash_attack_id, slash_attack) in (
@@ -80,11 +86,26 @@ impl<'a> System<'a> for AttackUpdates {
 			*thrust_attack_position = ThrustAttack::compute_position(&source_position, &source_direction, &source_collider);
 			*thrust_attack_collider = ThrustAttack::compute_collider(&source_direction);
 		}
+		// Spin attack updates. The spin stays centered on its source.
+		for (spin_attack_id, spin_attack) in (
+			&entities,
+			&sto_spin_attack,
+		).join() {
+			let source_position = *sto_position.get(spin_attack.source_id()).unwrap();
+			*sto_position.get_mut(spin_attack_id).unwrap() = source_position;
+		}
 		// Shield updates.
 		for (shield_id, shield) in (
 			&entities,
-			&sto_shield,
+			&mut sto_shield,
 		).join() {
+			// Recover stamina and show the shield only while it's raised.
+			shield.update();
+			if shield.is_raised() {
+				sto_hidden.remove(shield_id);
+			} else {
+				sto_hidden.insert(shield_id, Hidden).unwrap();
+			}
 			let bearer_id = shield.bearer_id();
 			// Get bearer data.
 			let bearer_position = *sto_position.get(bearer_id).unwrap();
@@ -1,11 +1,21 @@
-use crate::component::{
-	Health,
-	removal::TiedToEntity,
+use crate::{
+	component::{
+		BaseTint,
+		Enemy,
+		Health,
+		Hero,
+		HeroState,
+		removal::TiedToEntity,
+		SpawnIndex,
+		Velocity,
+	},
+	constants::*,
+	resource::RegionStates,
 };
 
 use amethyst::{
 	derive::SystemDesc,
-	ecs::{Entities, Join, ReadStorage, System, SystemData, WriteStorage},
+	ecs::{Entities, Join, ReadStorage, System, SystemData, Write, WriteStorage},
 	utils::removal::{Removal, exec_removal},
 };
 
@@ -18,12 +28,57 @@ impl<'a> System<'a> for Death {
 		Entities<'a>,
 		WriteStorage<'a, Removal<TiedToEntity>>,
 		ReadStorage<'a, Health>,
+		ReadStorage<'a, Enemy>,
+		ReadStorage<'a, SpawnIndex>,
+		Write<'a, RegionStates>,
+		WriteStorage<'a, Hero>,
+		WriteStorage<'a, Velocity>,
+		WriteStorage<'a, BaseTint>,
 	);
 
-	fn run(&mut self, (entities, mut sto_removal_tied_to_entity, sto_health): Self::SystemData) {
+	fn run(&mut self, (
+		entities,
+		mut sto_removal_tied_to_entity,
+		sto_health,
+		sto_enemy,
+		sto_spawn_index,
+		mut region_states,
+		mut sto_hero,
+		mut sto_velocity,
+		mut sto_base_tint,
+	): Self::SystemData) {
 		// Kill entities with zero health.
-		for (id, health) in (&entities, &sto_health).join() {
+		for (id, health, spawn_index) in (&entities, &sto_health, sto_spawn_index.maybe()).join() {
 			if health.current() == 0 {
+				// Heroes aren't deleted. Instead they play a death animation, after which the game is over.
+				if let Some(hero) = sto_hero.get_mut(id) {
+					let attack_id = match hero.state {
+						// Already dying or dead.
+						HeroState::Dying { .. } | HeroState::Dead => continue,
+						HeroState::FreelyMoving | HeroState::Rolling { .. } => None,
+						HeroState::Slashing { slash_attack_id, .. } => Some(slash_attack_id),
+						HeroState::Thrusting { thrust_attack_id, .. } => Some(thrust_attack_id),
+						HeroState::Charging => {
+							// Stop showing the charge.
+							sto_base_tint.remove(id);
+							None
+						},
+						HeroState::Spinning { spin_attack_id, .. } => Some(spin_attack_id),
+					};
+					// Cancel any attack in progress.
+					if let Some(attack_id) = attack_id {
+						entities.delete(attack_id).unwrap();
+					}
+					hero.state = HeroState::Dying { frames_left: HERO_DEATH_FRAMES };
+					if let Some(velocity) = sto_velocity.get_mut(id) {
+						*velocity = Velocity::default();
+					}
+					continue;
+				}
+				// Remember that this enemy was killed.
+				if let Some(spawn_index) = spawn_index.filter(|_| sto_enemy.contains(id)) {
+					region_states.kill_enemy(spawn_index.0);
+				}
 				// Delete this entity and any entities that depend on it.
 				entities.delete(id).unwrap();
 				exec_removal(&entities, &mut sto_removal_tied_to_entity, TiedToEntity(id));
@@ -1,9 +1,12 @@
 use crate::{
 	component::{
-		ArrowAttack,
+		Bomb,
+		Boomerang,
 		collider::{
+			DiskCollider,
 			HalfDiskCollider,
 			RectangleCollider,
+			rect_intersects_disk,
 			rect_intersects_half_disk,
 			rect_rect_intersection_area,
 		},
@@ -13,18 +16,27 @@ use crate::{
 		Health,
 		Heart,
 		Hero,
+		HitReaction,
 		Invulnerable,
 		KnockedBack,
 		Position,
+		Projectile,
+		ProjectileStopped,
+		Resistances,
 		SlashAttack,
+		SpinAttack,
 		ThrustAttack,
 		Shield,
+		SpawnIndex,
+		Velocity,
 	},
+	constants::*,
+	resource::RegionStates,
 };
 
 use amethyst::{
 	derive::SystemDesc,
-	ecs::{Entities, Join, ReadStorage, System, SystemData, WriteStorage},
+	ecs::{Entities, Entity, Join, ReadStorage, System, SystemData, Write, WriteStorage},
 };
 
 /// Does collision detection and response between moving bodies.
@@ -36,194 +48,342 @@ impl<'a> System<'a> for DynamicCollisionDetection {
 		Entities<'a>,
 		ReadStorage<'a, RectangleCollider>,
 		ReadStorage<'a, HalfDiskCollider>,
+		ReadStorage<'a, DiskCollider>,
 		WriteStorage<'a, Position>,
-		ReadStorage<'a, Direction>,
+		WriteStorage<'a, Velocity>,
+		WriteStorage<'a, Direction>,
 		ReadStorage<'a, Hero>,
 		ReadStorage<'a, Enemy>,
 		ReadStorage<'a, Faction>,
 		WriteStorage<'a, SlashAttack>,
 		WriteStorage<'a, ThrustAttack>,
+		WriteStorage<'a, SpinAttack>,
 		WriteStorage<'a, Shield>,
 		WriteStorage<'a, Health>,
 		WriteStorage<'a, Heart>,
 		WriteStorage<'a, KnockedBack>,
 		WriteStorage<'a, Invulnerable>,
-		ReadStorage<'a, ArrowAttack>,
+		WriteStorage<'a, Projectile>,
+		ReadStorage<'a, SpawnIndex>,
+		Write<'a, RegionStates>,
+		ReadStorage<'a, Bomb>,
+		WriteStorage<'a, Boomerang>,
+		ReadStorage<'a, Resistances>,
+		ReadStorage<'a, HitReaction>,
 	);
 
 	fn run(&mut self, (
 		entities,
 		sto_rectangle_collider,
 		sto_half_disk_collider,
+		sto_disk_collider,
 		sto_position,
-		sto_direction,
+		mut sto_velocity,
+		mut sto_direction,
 		sto_hero,
 		sto_enemy,
 		sto_faction,
 		mut sto_slash_attack,
 		mut sto_thrust_attack,
-		sto_shield,
+		mut sto_spin_attack,
+		mut sto_shield,
 		mut sto_health,
 		sto_heart,
 		mut sto_knocked_back,
 		mut sto_invulnerable,
-		sto_arrow_attack,
+		mut sto_projectile,
+		sto_spawn_index,
+		mut region_states,
+		sto_bomb,
+		mut sto_boomerang,
+		sto_resistances,
+		sto_hit_reaction,
 	): Self::SystemData) {
-		// Handle thrust attacks against enemies.
-		let mut max_overlap_area = 0.0;
-		let mut closest_colliding_enemy_id_and_position = None;
+		// Finds a raised shield borne by a character from another faction than `faction` that faces `direction`
+		// head on and passes the `overlaps` test, returning the IDs of the shield and its bearer.
+		let find_blocking_shield = |
+			faction: Faction,
+			direction: Direction,
+			overlaps: &dyn Fn(&RectangleCollider, &Position) -> bool,
+		| {
+			(&entities, &sto_shield, &sto_rectangle_collider, &sto_position, &sto_direction).join()
+				.find(|(_shield_id, shield, shield_collider, shield_position, shield_direction)| {
+					shield.is_raised()
+						&& *sto_faction.get(shield.bearer_id()).unwrap() != faction
+						&& direction == shield_direction.opposite()
+						&& overlaps(shield_collider, shield_position)
+				})
+				.map(|(shield_id, shield, _, _, _)| (shield_id, shield.bearer_id()))
+		};
+		// Reflected projectiles, as their IDs and the factions of the characters that reflected them.
+		let mut reflections = Vec::new();
+		// Blocked hits, as the IDs of the blocking shield and its bearer, the position of the hit, and the ID of the
+		// attacking character if it was close enough to be pushed back.
+		let mut blocks = Vec::new();
+		// Characters hit by attacks, as their IDs, the positions they were hit from, and the damage dealt before
+		// resistances.
+		let mut hits = Vec::new();
+		// Whether the character with the given ID can currently be hit by an attack.
+		let is_vulnerable = |target_id| {
+			!sto_invulnerable.contains(target_id)
+				&& sto_health.get(target_id).map_or(false, |health| health.current() > 0)
+		};
+		// Handle thrust attacks against characters from other factions.
 		for (
 			thrust_attack,
 			thrust_attack_collider,
 			thrust_attack_position,
+			thrust_attack_direction,
 		) in (
 			&mut sto_thrust_attack,
 			&sto_rectangle_collider,
 			&sto_position,
+			&sto_direction,
 		).join() {
 			// Ignore thrust attack collisions if it's inactive.
 			if !thrust_attack.is_active() {
 				continue;
 			}
-			// Find the closest enemy in collision with the thrust attack.
-			for (enemy_id, _enemy, enemy_collider, enemy_position) in (
+			// A shield facing the thrust stops it.
+			let overlaps_thrust_attack = |collider: &RectangleCollider, position: &Position| {
+				rect_rect_intersection_area((&thrust_attack_collider, &thrust_attack_position), (collider, position)) > 0.0
+			};
+			let faction = thrust_attack.faction();
+			if let Some((shield_id, bearer_id)) = find_blocking_shield(faction, *thrust_attack_direction, &overlaps_thrust_attack) {
+				blocks.push((shield_id, bearer_id, *thrust_attack_position, Some(thrust_attack.source_id())));
+				thrust_attack.make_inactive();
+				continue;
+			}
+			// Find the closest target in collision with the thrust attack.
+			let mut max_overlap_area = 0.0;
+			let mut closest_colliding_target_id = None;
+			for (target_id, target_faction, target_collider, target_position) in (
 				&entities,
-				&sto_enemy,
+				&sto_faction,
 				&sto_rectangle_collider,
 				&sto_position,
 			).join() {
+				// Ignore collisions with the same faction or with characters that can't be hit.
+				if thrust_attack.faction() == *target_faction || !is_vulnerable(target_id) {
+					continue;
+				}
 				let overlap_area = rect_rect_intersection_area(
 					(&thrust_attack_collider, &thrust_attack_position),
-					(&enemy_collider, &enemy_position),
+					(&target_collider, &target_position),
 				);
 				if overlap_area > max_overlap_area {
 					max_overlap_area = overlap_area;
-					closest_colliding_enemy_id_and_position = Some((enemy_id, enemy_position));
+					closest_colliding_target_id = Some(target_id);
 				}
 			}
-			// If any enemies were close enough for a collision, damage and knock back the closest one.
-			if let Some((enemy_id, enemy_position)) = closest_colliding_enemy_id_and_position {
-				// Damage and knock back enemy.
-				sto_knocked_back.insert(enemy_id, KnockedBack::from_positions(thrust_attack_position, enemy_position)).unwrap();
-				sto_health.get_mut(enemy_id).unwrap().damage(1);
-				// Make the thrust attack inactive now that it has hit an enemy.
+			// If any targets were close enough for a collision, hit the closest one.
+			if let Some(target_id) = closest_colliding_target_id {
+				hits.push((target_id, *thrust_attack_position, thrust_attack.damage()));
+				// Make the thrust attack inactive now that it has hit a target.
 				thrust_attack.make_inactive();
 			}
 		}
-		// Handle slash attacks against enemies.
+		// Handle slash attacks against characters from other factions.
 		for (slash_attack, slash_attack_collider, slash_attack_position) in (
 			&mut sto_slash_attack,
 			&sto_half_disk_collider,
 			&sto_position,
 		).join() {
-			// Damage and knock back any enemies in collision with the slash attack.
-			for (enemy_id, _enemy, enemy_collider, enemy_position) in (
+			// A shield facing the slash protects its bearer.
+			let overlaps_slash_attack = |collider: &RectangleCollider, position: &Position| {
+				rect_intersects_half_disk((collider, position), (&slash_attack_collider, &slash_attack_position))
+			};
+			let faction = slash_attack.faction();
+			if let Some((shield_id, bearer_id)) = find_blocking_shield(faction, slash_attack_collider.direction, &overlaps_slash_attack) {
+				if !slash_attack.has_been_hit(bearer_id) {
+					blocks.push((shield_id, bearer_id, *slash_attack_position, Some(slash_attack.source_id())));
+					slash_attack.mark_as_hit(bearer_id);
+				}
+			}
+			// Hit any targets in collision with the slash attack.
+			for (target_id, target_faction, target_collider, target_position) in (
 				&entities,
-				&sto_enemy,
+				&sto_faction,
 				&sto_rectangle_collider,
 				&sto_position,
 			).join() {
-				// Ignore collisions with enemies that have already been hit.
-				if slash_attack.has_been_hit(enemy_id) {
+				// Ignore collisions with the same faction or with characters that can't or have already been hit.
+				if faction == *target_faction || !is_vulnerable(target_id) || slash_attack.has_been_hit(target_id) {
 					continue;
 				}
-				let intersecting = rect_intersects_half_disk(
-					(&enemy_collider, &enemy_position),
-					(&slash_attack_collider, &slash_attack_position),
-				);
-				if intersecting {
-					// Knock back and damage enemy.
-					sto_knocked_back.insert(enemy_id, KnockedBack::from_positions(slash_attack_position, enemy_position)).unwrap();
-					sto_health.get_mut(enemy_id).unwrap().damage(1);
-					// Mark this enemy has having been hit by this attack.
-					slash_attack.mark_as_hit(enemy_id);
+				if overlaps_slash_attack(target_collider, target_position) {
+					hits.push((target_id, *slash_attack_position, slash_attack.damage()));
+					// Mark this target has having been hit by this attack.
+					slash_attack.mark_as_hit(target_id);
 				}
 			}
 		}
-		// Handle arrow collisions.
-		for (arrow_attack_id, arrow_attack, arrow_attack_collider, arrow_attack_position, arrow_attack_direction) in (
-			&entities,
-			&sto_arrow_attack,
-			&sto_rectangle_collider,
+		// Handle spin attacks, which sweep all the way around their source and so can't be blocked by a shield.
+		for (spin_attack, spin_attack_collider, spin_attack_position) in (
+			&mut sto_spin_attack,
+			&sto_disk_collider,
 			&sto_position,
-			&sto_direction,
 		).join() {
-			// If the arrow hits a shield from another faction, destroy the arrow.
-			let mut arrow_attack_blocked = false;
-			for (shield, shield_collider, shield_position, shield_direction) in (
-				&sto_shield,
+			for (target_id, target_faction, target_collider, target_position) in (
+				&entities,
+				&sto_faction,
 				&sto_rectangle_collider,
 				&sto_position,
-				&sto_direction,
 			).join() {
-				// Ignore collisions with shields from the same faction.
-				let bearer_faction = sto_faction.get(shield.bearer_id()).unwrap();
-				if arrow_attack.faction() == *bearer_faction {
+				// Ignore collisions with the same faction or with characters that can't or have already been hit.
+				if spin_attack.faction() == *target_faction || !is_vulnerable(target_id) || spin_attack.has_been_hit(target_id) {
 					continue;
 				}
-				// Ignore unopposing collisions.
-				if *arrow_attack_direction != shield_direction.opposite() {
-					continue;
-				}
-				let overlap_area = rect_rect_intersection_area(
-					(&arrow_attack_collider, &arrow_attack_position),
-					(&shield_collider, &shield_position),
-				);
-				if overlap_area > 0.0 {
-					arrow_attack_blocked = true;
-					entities.delete(arrow_attack_id).unwrap();
+				if rect_intersects_disk((target_collider, target_position), (&spin_attack_collider, &spin_attack_position)) {
+					hits.push((target_id, *spin_attack_position, spin_attack.damage()));
+					spin_attack.mark_as_hit(target_id);
 				}
 			}
-			if arrow_attack_blocked {
+		}
+		// Handle projectile collisions.
+		for (projectile_id, projectile, projectile_collider, projectile_position, projectile_direction) in (
+			&entities,
+			&mut sto_projectile,
+			&sto_rectangle_collider,
+			&sto_position,
+			&sto_direction,
+		).join() {
+			// If the projectile hits a shield from another faction head on, reflect it if the shield can, and destroy it
+			// otherwise.
+			let overlaps_projectile = |collider: &RectangleCollider, position: &Position| {
+				rect_rect_intersection_area((&projectile_collider, &projectile_position), (collider, position)) > 0.0
+			};
+			if let Some((shield_id, bearer_id)) = find_blocking_shield(projectile.faction(), *projectile_direction, &overlaps_projectile) {
+				if sto_shield.get(shield_id).unwrap().reflects() {
+					reflections.push((projectile_id, *sto_faction.get(bearer_id).unwrap()));
+				} else {
+					blocks.push((shield_id, bearer_id, *projectile_position, None));
+					entities.delete(projectile_id).unwrap();
+				}
 				continue;
 			}
 			// Otherwise, check for collisions with characters from other factions.
 			let mut max_overlap_area = 0.0;
-			let mut closest_colliding_target_id_and_position = None;
+			let mut closest_colliding_target_id = None;
 			for (target_id, target_faction, target_collider, target_position) in (
 				&entities,
 				&sto_faction,
 				&sto_rectangle_collider,
 				&sto_position,
 			).join() {
-				// Ignore collisions with entities from the same faction.
-				if arrow_attack.faction() == *target_faction {
+				// Ignore collisions with entities from the same faction, that can't be hurt right now, or that this
+				// projectile already passed through.
+				if projectile.faction() == *target_faction || !is_vulnerable(target_id) || projectile.has_been_hit(target_id) {
 					continue;
 				}
 				let overlap_area = rect_rect_intersection_area(
-					(&arrow_attack_collider, &arrow_attack_position),
+					(&projectile_collider, &projectile_position),
 					(&target_collider, &target_position),
 				);
 				if overlap_area > max_overlap_area {
 					max_overlap_area = overlap_area;
-					closest_colliding_target_id_and_position = Some((target_id, target_position));
+					closest_colliding_target_id = Some(target_id);
 				}
 			}
-			// If any targets were close enough for a collision, damage and knock back the closest one.
-			if let Some((target_id, target_position)) = closest_colliding_target_id_and_position {
-				// Damage and knock back target.
-				sto_knocked_back.insert(target_id, KnockedBack::from_positions(arrow_attack_position, &target_position)).unwrap();
-				sto_health.get_mut(target_id).unwrap().damage(1);
-				// Destroy the arrow attack.
-				entities.delete(arrow_attack_id).unwrap();
+			// If any targets were close enough for a collision, hit the closest one.
+			if let Some(target_id) = closest_colliding_target_id {
+				hits.push((target_id, *projectile_position, projectile.damage()));
+				// Destroy the projectile unless it can pierce the target.
+				if let ProjectileStopped::Yes = projectile.hit(target_id) {
+					entities.delete(projectile_id).unwrap();
+				}
+			}
+		}
+		// Reflected projectiles fly back at their shooters, and can now hit them.
+		for (projectile_id, faction) in reflections {
+			sto_projectile.get_mut(projectile_id).unwrap().reflect(
+				faction,
+				sto_velocity.get_mut(projectile_id).unwrap(),
+				sto_direction.get_mut(projectile_id).unwrap(),
+			);
+		}
+		// Blocking costs shield stamina and pushes the bearer and the attacker apart.
+		for (shield_id, bearer_id, hit_position, attacker_id) in blocks {
+			sto_shield.get_mut(shield_id).unwrap().block();
+			let bearer_position = *sto_position.get(bearer_id).unwrap();
+			knock_back(&mut sto_knocked_back, &sto_hit_reaction, bearer_id, &hit_position, &bearer_position);
+			if let Some(attacker_id) = attacker_id {
+				if let Some(attacker_position) = sto_position.get(attacker_id) {
+					knock_back(&mut sto_knocked_back, &sto_hit_reaction, attacker_id, &bearer_position, attacker_position);
+				}
+			}
+		}
+		// Handle bomb explosions.
+		for (bomb_id, bomb, bomb_position) in (
+			&entities,
+			&sto_bomb,
+			&sto_position,
+		).join() {
+			if !bomb.is_exploding() {
+				continue;
+			}
+			// Hit any living characters from other factions within the blast radius.
+			for (target_id, target_faction, target_position, target_health) in (
+				&entities,
+				&sto_faction,
+				&sto_position,
+				&sto_health,
+			).join() {
+				if bomb.faction() == *target_faction || target_health.current() == 0 {
+					continue;
+				}
+				let distance_squared = (target_position.x - bomb_position.x).powi(2)
+					+ (target_position.y - bomb_position.y).powi(2);
+				if distance_squared <= BOMB_BLAST_RADIUS.powi(2) {
+					hits.push((target_id, *bomb_position, bomb.damage()));
+				}
+			}
+			// Destroy the exploded bomb.
+			entities.delete(bomb_id).unwrap();
+		}
+		// Handle boomerang collisions.
+		for (boomerang, boomerang_collider, boomerang_position) in (
+			&mut sto_boomerang,
+			&sto_rectangle_collider,
+			&sto_position,
+		).join() {
+			// Hit any characters from other factions in collision with the boomerang.
+			for (target_id, target_faction, target_collider, target_position) in (
+				&entities,
+				&sto_faction,
+				&sto_rectangle_collider,
+				&sto_position,
+			).join() {
+				// Ignore collisions with the same faction or with characters that have already been hit.
+				if boomerang.faction() == *target_faction || boomerang.has_been_hit(target_id) {
+					continue;
+				}
+				let overlap_area = rect_rect_intersection_area(
+					(&boomerang_collider, &boomerang_position),
+					(&target_collider, &target_position),
+				);
+				if overlap_area > 0.0 {
+					hits.push((target_id, *boomerang_position, boomerang.damage()));
+					boomerang.mark_as_hit(target_id);
+				}
 			}
 		}
 		// Handle hero-enemy collisions.
 		let mut max_overlap_area = 0.0;
-		let mut closest_colliding_position = None;
+		let mut closest_colliding_enemy = None;
 		for (hero_id, _hero, hero_collider, hero_position, hero_health) in (
 			&entities,
 			&sto_hero,
 			&sto_rectangle_collider,
 			&sto_position,
-			&mut sto_health,
+			&sto_health,
 		).join() {
-			// Ignore invulnerable heroes.
-			if sto_invulnerable.contains(hero_id) {
+			// Ignore invulnerable or dead heroes.
+			if sto_invulnerable.contains(hero_id) || hero_health.current() == 0 {
 				continue;
 			}
 			// Find the closest enemy in collision with the hero.
-			for (enemy_id, _enemy, enemy_collider, enemy_position) in (
+			for (enemy_id, enemy, enemy_collider, enemy_position) in (
 				&entities,
 				&sto_enemy,
 				&sto_rectangle_collider,
@@ -239,16 +399,45 @@ impl<'a> System<'a> for DynamicCollisionDetection {
 				);
 				if overlap_area > max_overlap_area {
 					max_overlap_area = overlap_area;
-					closest_colliding_position = Some(enemy_position);
+					closest_colliding_enemy = Some((enemy_id, enemy, enemy_position));
 				}
 			}
-			// If any enemies were close enough for a collision, knock the hero back from the closest one.
-			if let Some(enemy_position) = closest_colliding_position {
-				// Damage and knock back hero.
-				sto_knocked_back.insert(hero_id, KnockedBack::from_positions(enemy_position, hero_position)).unwrap();
-				hero_health.damage(1);
-				// Give hero invulnerability.
-				sto_invulnerable.insert(hero_id, Invulnerable::new()).unwrap();
+			// If any enemies were close enough for a collision, the closest one hits the hero.
+			if let Some((enemy_id, enemy, enemy_position)) = closest_colliding_enemy {
+				// A raised shield facing the enemy blocks the hit, pushing both apart.
+				let enemy_direction = Direction::nearest(enemy_position.x - hero_position.x, enemy_position.y - hero_position.y);
+				let blocking_shield_id = (&entities, &sto_shield, &sto_direction).join()
+					.find(|(_shield_id, shield, shield_direction)| {
+						shield.bearer_id() == hero_id && shield.is_raised() && **shield_direction == enemy_direction
+					})
+					.map(|(shield_id, _shield, _shield_direction)| shield_id);
+				if let Some(shield_id) = blocking_shield_id {
+					sto_shield.get_mut(shield_id).unwrap().block();
+					knock_back(&mut sto_knocked_back, &sto_hit_reaction, hero_id, enemy_position, hero_position);
+					knock_back(&mut sto_knocked_back, &sto_hit_reaction, enemy_id, hero_position, enemy_position);
+					continue;
+				}
+				hits.push((hero_id, *enemy_position, enemy.contact_damage));
+			}
+		}
+		// Damage and knock back every character hit this frame, reduced or increased by its resistances. Characters
+		// then become invulnerable for a while, according to their hit reactions. Hits that are fully resisted have
+		// no effect.
+		for (target_id, hit_position, damage) in hits {
+			if sto_invulnerable.contains(target_id) {
+				continue;
+			}
+			let amount = sto_resistances.get(target_id).map_or(damage.amount, |resistances| resistances.apply(damage));
+			if amount == 0 {
+				continue;
+			}
+			let target_position = *sto_position.get(target_id).unwrap();
+			knock_back(&mut sto_knocked_back, &sto_hit_reaction, target_id, &hit_position, &target_position);
+			sto_health.get_mut(target_id).unwrap().damage(amount);
+			if let Some(hit_reaction) = sto_hit_reaction.get(target_id) {
+				if hit_reaction.invulnerable_frames > 0 {
+					sto_invulnerable.insert(target_id, Invulnerable::new(hit_reaction.invulnerable_frames)).unwrap();
+				}
 			}
 		}
 		// Handle hero-heart collisions.
@@ -258,8 +447,12 @@ impl<'a> System<'a> for DynamicCollisionDetection {
 			&sto_position,
 			&mut sto_health,
 		).join() {
+			// Dead heroes can't pick up hearts.
+			if hero_health.current() == 0 {
+				continue;
+			}
 			// For each heart in contact with the hero, destroy the heart and increase the hero's health.
-			for (heart_id, _heart, heart_collider, heart_position) in (
+			for (heart_id, heart, heart_collider, heart_position) in (
 				&entities,
 				&sto_heart,
 				&sto_rectangle_collider,
@@ -271,9 +464,28 @@ impl<'a> System<'a> for DynamicCollisionDetection {
 				);
 				if overlap_area > 0.0 {
 					entities.delete(heart_id).unwrap();
-					hero_health.heal(1);
+					hero_health.heal(heart.heal);
+					// Remember that this heart was collected.
+					if let Some(spawn_index) = sto_spawn_index.get(heart_id) {
+						region_states.collect_heart(spawn_index.0);
+					}
 				}
 			}
 		}
 	}
 }
+
+/// Knocks the character `id` at `position` away from `from`, if its hit reaction calls for any knockback.
+fn knock_back(
+	sto_knocked_back: &mut WriteStorage<KnockedBack>,
+	sto_hit_reaction: &ReadStorage<HitReaction>,
+	id: Entity,
+	from: &Position,
+	position: &Position,
+) {
+	if let Some(hit_reaction) = sto_hit_reaction.get(id) {
+		if hit_reaction.knockback_frames > 0 {
+			sto_knocked_back.insert(id, KnockedBack::from_positions(from, position, hit_reaction)).unwrap();
+		}
+	}
+}
@@ -1,131 +1,248 @@
 use crate::{
 	component::{
-		animation::{DirectionalAnimation, DirectionalFrame},
-		ArrowAttack,
-		behavior::{ShootArrows, ShouldShootArrow, Wander},
-		collider::RectangleCollider,
+		animation::DirectionalAnimation,
+		BaseTint,
+		behavior::{BehaviorContext, BehaviorTree, MeleeAttack, Waypoints},
+		collider::{HalfDiskCollider, RectangleCollider},
 		Direction,
+		Enemy,
 		Faction,
+		Health,
+		Hero,
 		KnockedBack,
 		Position,
-		removal::TiedToRegion,
+		Projectile,
+		removal::{TiedToEntity, TiedToRegion},
+		SlashAttack,
+		Terrain,
+		ThrustAttack,
 		Velocity,
 	},
 	constants::*,
-	resource::SpriteSheets,
+	resource::{CurrentRegion, GameRng, SpriteSheets},
 };
 
 use amethyst::{
 	derive::SystemDesc,
-	ecs::{Entities, Join, ReadExpect, ReadStorage, System, SystemData, WriteStorage},
+	ecs::{Entities, Join, ReadExpect, ReadStorage, System, SystemData, WriteExpect, WriteStorage},
 	utils::removal::Removal,
 };
-use rand::Rng;
 
 /// Controls the hero character based on player input.
 #[derive(SystemDesc)]
-pub struct EnemyControl;
+pub struct EnemyControl {
+	/// The number of frames this system has run.
+	frame: u64,
+}
+
+impl EnemyControl {
+	pub fn new() -> Self {
+		Self {
+			frame: 0,
+		}
+	}
+}
 
 impl<'a> System<'a> for EnemyControl {
 	type SystemData = (
 		Entities<'a>,
 		ReadExpect<'a, SpriteSheets>,
+		WriteExpect<'a, GameRng>,
 		WriteStorage<'a, Removal<TiedToRegion>>,
-		WriteStorage<'a, Wander>,
-		WriteStorage<'a, ShootArrows>,
+		ReadStorage<'a, Enemy>,
 		ReadStorage<'a, KnockedBack>,
 		WriteStorage<'a, Direction>,
 		WriteStorage<'a, Position>,
 		WriteStorage<'a, Velocity>,
 		WriteStorage<'a, RectangleCollider>,
-		WriteStorage<'a, ArrowAttack>,
+		WriteStorage<'a, Projectile>,
 		WriteStorage<'a, DirectionalAnimation>,
+		WriteExpect<'a, CurrentRegion>,
+		ReadStorage<'a, Terrain>,
+		ReadStorage<'a, Hero>,
+		ReadStorage<'a, Health>,
+		ReadStorage<'a, Waypoints>,
+		WriteStorage<'a, BaseTint>,
+		WriteStorage<'a, BehaviorTree>,
+		(
+			WriteStorage<'a, SlashAttack>,
+			WriteStorage<'a, ThrustAttack>,
+			WriteStorage<'a, HalfDiskCollider>,
+			WriteStorage<'a, Removal<TiedToEntity>>,
+			ReadStorage<'a, Faction>,
+		),
 	);
 
 	fn run(&mut self, (
 		entities,
 		sprite_sheets,
+		mut rng,
 		mut sto_removal_tied_to_region,
-		mut sto_wander,
-		mut sto_shoot_arrows,
+		sto_enemy,
 		sto_knocked_back,
 		mut sto_direction,
 		mut sto_position,
 		mut sto_velocity,
 		mut sto_rectangle_collider,
-		mut sto_arrow_attack,
+		mut sto_projectile,
 		mut sto_directional_animation,
+		mut current_region,
+		sto_terrain,
+		sto_hero,
+		sto_health,
+		sto_waypoints,
+		mut sto_base_tint,
+		mut sto_behavior_tree,
+		(
+			mut sto_slash_attack,
+			mut sto_thrust_attack,
+			mut sto_half_disk_collider,
+			mut sto_removal_tied_to_entity,
+			sto_faction,
+		),
 	): Self::SystemData) {
-		const SPEED: f32 = 3.0;
-		const TURN_THRESHOLD: f32 = 0.01;
-		const STOP_THRESHOLD: f32 = TURN_THRESHOLD + 0.01;
-		// Wander.
-		let mut rng = rand::thread_rng();
-		for (wander, _no_knocked_back, direction, velocity) in (
-			&mut sto_wander,
+		self.frame += 1;
+
+		// Enemies stand still unless their behavior trees move them.
+		for (_enemy, _no_knocked_back, velocity) in (&sto_enemy, !&sto_knocked_back, &mut sto_velocity).join() {
+			*velocity = Velocity::default();
+		}
+		// Find living heroes, for behaviors that react to the hero.
+		let hero_positions: Vec<Position> = (&sto_hero, &sto_health, &sto_position).join()
+			.filter(|(_hero, health, _position)| health.current() > 0)
+			.map(|(_hero, _health, position)| *position)
+			.collect();
+		let nearest_hero_position = |position: &Position| {
+			hero_positions.iter().copied().min_by(|a, b| {
+				position.distance_to(a).partial_cmp(&position.distance_to(b)).unwrap()
+			})
+		};
+		// Run behavior trees. Projectiles and melee attacks are spawned after the loop, since spawning uses storages the
+		// loop borrows.
+		let mut shots = Vec::new();
+		let mut melee_attacks = Vec::new();
+		for (source_id, enemy, behavior_tree, waypoints, _no_knocked_back, position, direction, velocity) in (
+			&entities,
+			&sto_enemy,
+			&mut sto_behavior_tree,
+			sto_waypoints.maybe(),
 			// No control while being knocked back
 			!&sto_knocked_back,
+			&sto_position,
 			&mut sto_direction,
 			&mut sto_velocity,
 		).join() {
-			// Randomly change wander direction or stop.
-			let p = rng.gen::<f32>();
-			if (0.0..TURN_THRESHOLD).contains(&p) {
-				wander.direction = Some(rng.gen());
-			} else if (TURN_THRESHOLD..STOP_THRESHOLD).contains(&p) {
-				wander.direction = None;
+			let hero_position = nearest_hero_position(position);
+			let hero_in_sight = hero_position.map_or(false, |hero_position| {
+				current_region.get().has_line_of_sight(&sto_terrain, *position, hero_position)
+			});
+			let hero_in_line = hero_positions.iter().any(|hero_position| {
+				current_region.get().has_line_of_fire(&sto_terrain, *position, *hero_position)
+			});
+			let was_telegraphing = behavior_tree.is_telegraphing();
+			let thrust_active = behavior_tree.attack_id()
+				.and_then(|attack_id| sto_thrust_attack.get(attack_id))
+				.map_or(false, ThrustAttack::is_active);
+			let mut context = BehaviorContext {
+				rng: &mut *rng,
+				current_region: &mut *current_region,
+				frame: self.frame,
+				speed: enemy.speed,
+				position: *position,
+				hero_position,
+				hero_in_sight,
+				hero_in_line,
+				waypoints: waypoints.map_or(&[][..], |waypoints| waypoints.0.as_slice()),
+				direction: *direction,
+				velocity: Velocity::default(),
+				shot: None,
+				telegraphing: false,
+				melee_attack: None,
+				melee_attack_finished: false,
+				thrust_active,
+			};
+			behavior_tree.tick(&mut context);
+			*direction = context.direction;
+			*velocity = context.velocity;
+			if context.telegraphing {
+				sto_base_tint.insert(source_id, BaseTint(TELEGRAPH_TINT)).unwrap();
+			} else if was_telegraphing {
+				sto_base_tint.remove(source_id);
 			}
-			// Move according to current wander direction.
-			match wander.direction {
-				Some(wander_direction) => {
-					*direction = wander_direction;
-					*velocity = match wander_direction {
-						Direction::Up => Velocity { x: 0.0, y: SPEED },
-						Direction::Down => Velocity { x: 0.0, y: -SPEED },
-						Direction::Left => Velocity { x: -SPEED, y: 0.0 },
-						Direction::Right => Velocity { x: SPEED, y: 0.0 },
-					}
-				},
-				None => *velocity = Velocity::default(),
+			if let Some(shot) = context.shot {
+				shots.push((source_id, shot));
 			}
-		}
-		// Shoot arrows.
-		for (source_id, shoot_arrows, _no_knocked_back) in (
-			&entities,
-			&mut sto_shoot_arrows,
-			// No control while being knocked back
-			!&sto_knocked_back,
-		).join() {
-			if let ShouldShootArrow::Yes = shoot_arrows.update() {
-				let position = *sto_position.get(source_id).unwrap();
-				let direction = *sto_direction.get(source_id).unwrap();
-				let velocity = match direction {
-					Direction::Up => Velocity { x: 0.0, y: ARROW_SPEED },
-					Direction::Down => Velocity { x: 0.0, y: -ARROW_SPEED },
-					Direction::Left => Velocity { x: -ARROW_SPEED, y: 0.0 },
-					Direction::Right => Velocity { x: ARROW_SPEED, y: 0.0 },
-				};
-				let collider = ArrowAttack::compute_collider(&direction);
-				entities
-					.build_entity()
-					.with(ArrowAttack::new(Faction::Enemy), &mut sto_arrow_attack)
-					.with(Removal::new(TiedToRegion), &mut sto_removal_tied_to_region)
-					.with(position, &mut sto_position)
-					.with(velocity, &mut sto_velocity)
-					.with(direction, &mut sto_direction)
-					.with(collider, &mut sto_rectangle_collider)
-					.with(DirectionalAnimation::new(
-						sprite_sheets.arrow_attack.clone(),
-						vec!(DirectionalFrame {
-							up: 0,
-							down: 1,
-							left: 2,
-							right: 3,
-							duration: None,
-						}),
-					), &mut sto_directional_animation)
-					.build();
+			if context.melee_attack_finished {
+				if let Some(attack_id) = behavior_tree.take_attack_id() {
+					entities.delete(attack_id).unwrap();
+				}
+			}
+			if let Some(melee_attack) = context.melee_attack {
+				melee_attacks.push((source_id, melee_attack, *position, *direction));
 			}
 		}
+		// Start melee attacks.
+		for (source_id, melee_attack, position, direction) in melee_attacks {
+			let faction = *sto_faction.get(source_id).unwrap();
+			let attack_id = match melee_attack {
+				MeleeAttack::Slash(damage) => SlashAttack::spawn(
+					&entities,
+					sprite_sheets.slash_attack.clone(),
+					source_id,
+					faction,
+					damage,
+					SLASH_ATTACK_RADIUS,
+					position,
+					direction,
+					(
+						&mut sto_slash_attack,
+						&mut sto_removal_tied_to_region,
+						&mut sto_removal_tied_to_entity,
+						&mut sto_position,
+						&mut sto_direction,
+						&mut sto_half_disk_collider,
+						&mut sto_directional_animation,
+					),
+				),
+				MeleeAttack::Thrust(damage) => ThrustAttack::spawn(
+					&entities,
+					sprite_sheets.thrust_attack.clone(),
+					source_id,
+					faction,
+					damage,
+					position,
+					direction,
+					(
+						&mut sto_thrust_attack,
+						&mut sto_removal_tied_to_region,
+						&mut sto_removal_tied_to_entity,
+						&mut sto_position,
+						&mut sto_direction,
+						&mut sto_rectangle_collider,
+						&mut sto_directional_animation,
+					),
+				),
+			};
+			sto_behavior_tree.get_mut(source_id).unwrap().set_attack_id(attack_id);
+		}
+		for (source_id, (position, direction, projectile)) in shots {
+			Projectile::spawn(
+				&entities,
+				sprite_sheets.arrow_attack.clone(),
+				projectile,
+				*sto_faction.get(source_id).unwrap(),
+				position,
+				direction,
+				(
+					&mut sto_projectile,
+					&mut sto_removal_tied_to_region,
+					&mut sto_position,
+					&mut sto_velocity,
+					&mut sto_direction,
+					&mut sto_rectangle_collider,
+					&mut sto_directional_animation,
+				),
+			);
+		}
 	}
 }
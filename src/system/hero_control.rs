@@ -1,49 +1,73 @@
 use crate::{
 	component::{
-		animation::{DirectionalAnimation, DirectionalFrame},
-		collider::{HalfDiskCollider, RectangleCollider},
+		animation::{DirectionalAnimation, SimpleAnimation, SimpleFrame},
+		BaseTint,
+		Bomb,
+		Boomerang,
+		collider::{DiskCollider, HalfDiskCollider, RectangleCollider},
+		Combo,
+		ComboChain,
 		Direction,
+		Faction,
 		Hero,
 		HeroState,
+		Invulnerable,
+		Item,
+		ItemSlot,
 		KnockedBack,
 		Position,
+		Projectile,
+		ProjectileConfig,
 		removal::{TiedToEntity, TiedToRegion},
+		Shield,
 		SlashAttack,
+		SpinAttack,
+		Strike,
+		StrikeAttack,
 		ThrustAttack,
 		Velocity,
+		WeaponDamage,
 	},
 	constants::*,
-	input_bindings::{InputBindings, Actions},
-	resource::SpriteSheets,
+	input_bindings::Actions,
+	resource::{ActionState, SpriteSheets},
 };
 
 use amethyst::{
 	derive::SystemDesc,
-	ecs::{Entities, Join, ReadExpect, ReadStorage, System, SystemData, WriteStorage},
-	input::InputHandler,
+	ecs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, SystemData, WriteStorage},
 	shred::Read,
 	utils::removal::Removal,
 };
 
-use std::convert::TryFrom;
+use std::{convert::TryFrom, f32::consts::FRAC_1_SQRT_2};
 
-/// Controls the hero character based on player input.
+/// Controls the hero character based on the current action state.
 #[derive(SystemDesc)]
 pub struct HeroControl {
 	primary_action_down_last_frame: bool,
+	/// The number of frames the primary action has been held down, for charging spin attacks.
+	primary_action_held_frames: u32,
+	secondary_action_down_last_frame: bool,
+	next_item_action_down_last_frame: bool,
+	roll_action_down_last_frame: bool,
 }
 
 impl HeroControl {
 	pub fn new() -> Self {
 		Self {
 			primary_action_down_last_frame: false,
+			primary_action_held_frames: 0,
+			secondary_action_down_last_frame: false,
+			next_item_action_down_last_frame: false,
+			roll_action_down_last_frame: false,
 		}
 	}
 }
 
 impl<'a> System<'a> for HeroControl {
 	type SystemData = (
-		Read<'a, InputHandler<InputBindings>>,
+		Read<'a, ActionState>,
 		Entities<'a>,
 		ReadExpect<'a, SpriteSheets>,
 		WriteStorage<'a, Removal<TiedToRegion>>,
@@ -58,10 +82,25 @@ impl<'a> System<'a> for HeroControl {
 		WriteStorage<'a, RectangleCollider>,
 		WriteStorage<'a, HalfDiskCollider>,
 		WriteStorage<'a, DirectionalAnimation>,
+		ReadStorage<'a, Faction>,
+		WriteStorage<'a, ItemSlot>,
+		WriteStorage<'a, Projectile>,
+		WriteStorage<'a, Bomb>,
+		WriteStorage<'a, Boomerang>,
+		WriteStorage<'a, SimpleAnimation>,
+		WriteStorage<'a, Shield>,
+		ReadStorage<'a, WeaponDamage>,
+		(
+			WriteStorage<'a, SpinAttack>,
+			WriteStorage<'a, DiskCollider>,
+			WriteStorage<'a, BaseTint>,
+			WriteStorage<'a, Combo>,
+			WriteStorage<'a, Invulnerable>,
+		),
 	);
 
 	fn run(&mut self, (
-		input,
+		actions,
 		entities,
 		sprite_sheets,
 		mut sto_removal_tied_to_region,
@@ -76,15 +115,47 @@ impl<'a> System<'a> for HeroControl {
 		mut sto_rectangle_collider,
 		mut sto_disk_arc_collider,
 		mut sto_directional_animation,
+		sto_faction,
+		mut sto_item_slot,
+		mut sto_projectile,
+		mut sto_bomb,
+		mut sto_boomerang,
+		mut sto_simple_animation,
+		mut sto_shield,
+		sto_weapon_damage,
+		(
+			mut sto_spin_attack,
+			mut sto_disk_collider,
+			mut sto_base_tint,
+			mut sto_combo,
+			mut sto_invulnerable,
+		),
 	): Self::SystemData) {
 		// Tuning parameters
 		const ORTHOGONAL_SPEED: f32 = 5.0;
-		const DIAGONAL_SPEED: f32 = 0.70710678118 * ORTHOGONAL_SPEED;
-		const SLASH_FRAMES: u32 = 9;
-		const THRUST_FRAMES: u32 = 9;
-		const THRUST_SPEED: f32 = 2.0 * ORTHOGONAL_SPEED;
+		const DIAGONAL_SPEED: f32 = FRAC_1_SQRT_2 * ORTHOGONAL_SPEED;
+		const CHARGE_FRAMES: u32 = 40;
+		const SPIN_FRAMES: u32 = 16;
+		const SPIN_TURN_PERIOD: u32 = 4;
+		const ROLL_FRAMES: u32 = 12;
+		// Limited at runtime by the hero's collider; see below.
+		const ROLL_SPEED: f32 = 12.0;
+		const ROLL_COOLDOWN_FRAMES: u32 = 30;
 
-		let primary_action_down = input.action_is_down(&Actions::Primary).unwrap_or(false);
+		let primary_action_down = actions.is_down(Actions::Primary);
+		let primary_action_pressed = !self.primary_action_down_last_frame && primary_action_down;
+		let secondary_action_down = actions.is_down(Actions::Secondary);
+		let next_item_action_down = actions.is_down(Actions::NextItem);
+		let strafe_action_down = actions.is_down(Actions::Strafe);
+		let roll_action_down = actions.is_down(Actions::Roll);
+		self.primary_action_held_frames = if primary_action_down {
+			self.primary_action_held_frames.saturating_add(1)
+		} else {
+			0
+		};
+
+		// Items are used after the hero loop, since using one creates entities in storages the loop borrows.
+		let mut item_uses = Vec::new();
 
 		for (hero_id, hero, _no_knocked_back, velocity) in (
 			&entities,
@@ -93,6 +164,17 @@ impl<'a> System<'a> for HeroControl {
 			!&sto_knock_back,
 			&mut sto_velocity,
 		).join() {
+			if let Some(item_slot) = sto_item_slot.get_mut(hero_id) {
+				item_slot.update();
+			}
+			hero.roll_cooldown_frames_left = hero.roll_cooldown_frames_left.saturating_sub(1);
+			// Sword attack presses are buffered, so they can chain into combos or start an attack once the hero is free.
+			if let Some(combo) = sto_combo.get_mut(hero_id) {
+				combo.update();
+				if primary_action_pressed {
+					combo.buffer_press();
+				}
+			}
 			match hero.state {
 				// Free for player to control
 				HeroState::FreelyMoving => {
@@ -100,20 +182,20 @@ impl<'a> System<'a> for HeroControl {
 					let hero_direction = sto_direction.get_mut(hero_id).unwrap();
 					let mut vx: i16 = 0;
 					let mut vy: i16 = 0;
-					if input.action_is_down(&Actions::Up).unwrap() {
+					if actions.is_down(Actions::Up) {
 						vy += 1;
 					}
-					if input.action_is_down(&Actions::Down).unwrap() {
+					if actions.is_down(Actions::Down) {
 						vy -= 1;
 					}
-					if input.action_is_down(&Actions::Left).unwrap() {
+					if actions.is_down(Actions::Left) {
 						vx -= 1;
 					}
-					if input.action_is_down(&Actions::Right).unwrap() {
+					if actions.is_down(Actions::Right) {
 						vx += 1;
 					}
 					// Update direction if needed. There are eight directions of movement but only four for animation.
-					if !input.action_is_down(&Actions::Strafe).unwrap() {
+					if !actions.is_down(Actions::Strafe) {
 						// Not strafing.
 						match (*hero_direction, vx, vy) {
 							// Moving in a cardinal direction.
@@ -142,71 +224,88 @@ impl<'a> System<'a> for HeroControl {
 						velocity.y = f32::try_from(vy).unwrap() * DIAGONAL_SPEED;
 					}
 					// Check for sword attack.
-					if !self.primary_action_down_last_frame && primary_action_down {
+					let attack_pressed = sto_combo.get_mut(hero_id).map_or(false, |combo| combo.take_buffered_press());
+					if attack_pressed {
 						let hero_direction = *sto_direction.get(hero_id).unwrap();
-						let hero_position = *sto_position.get(hero_id).unwrap();
 						let advancing = match hero_direction {
 							Direction::Up => vy == 1,
 							Direction::Down => vy == -1,
 							Direction::Left => vx == -1,
 							Direction::Right => vx == 1,
 						};
-						if advancing {
-							// Hero is advancing -> thrust attack.
-							let thrust_attack_animation = DirectionalAnimation::new(
-								sprite_sheets.thrust_attack.clone(),
-								vec!(DirectionalFrame {
-									up: 0,
-									down: 1,
-									left: 2,
-									right: 3,
-									duration: None,
-								}),
+						// Hero is advancing -> thrust combo. Hero is strafing/retreating/standing still -> slash combo.
+						let chain = if advancing { ComboChain::Thrust } else { ComboChain::Slash };
+						if let Some(strike) = sto_combo.get_mut(hero_id).unwrap().start(chain) {
+							let hero_faction = *sto_faction.get(hero_id).unwrap();
+							hero.state = start_strike(
+								&entities,
+								&sprite_sheets,
+								hero_id,
+								hero_faction,
+								strike,
+								(
+									&mut sto_slash_attack,
+									&mut sto_thrust_attack,
+									&mut sto_removal_tied_to_region,
+									&mut sto_removal_tied_to_entity,
+									&mut sto_position,
+									&mut sto_direction,
+									&mut sto_disk_arc_collider,
+									&mut sto_rectangle_collider,
+									&mut sto_directional_animation,
+								),
 							);
-							let thrust_attack_id = entities
-								.build_entity()
-								.with(ThrustAttack::new(hero_id), &mut sto_thrust_attack)
-								.with(Removal::new(TiedToRegion), &mut sto_removal_tied_to_region)
-								.with(Removal::new(TiedToEntity(hero_id)), &mut sto_removal_tied_to_entity)
-								.with(hero_position, &mut sto_position)
-								.with(hero_direction, &mut sto_direction)
-								.with(ThrustAttack::compute_collider(&hero_direction), &mut sto_rectangle_collider)
-								.with(thrust_attack_animation, &mut sto_directional_animation)
-								.build();
-							hero.state = HeroState::Thrusting {
-								thrust_attack_id: thrust_attack_id,
-								frames_left: THRUST_FRAMES,
-							};
+						}
+					}
+					// Check for dodge roll, in the direction of movement or else the direction faced.
+					let roll_pressed = !self.roll_action_down_last_frame && roll_action_down;
+					if hero.state == HeroState::FreelyMoving && roll_pressed && hero.roll_cooldown_frames_left == 0 {
+						// Each step must be shorter than the hero's collider half-extent, so that static collision
+						// detection can always push a rolling hero back out of a wall instead of through it.
+						let roll_speed = sto_rectangle_collider.get(hero_id).map_or(ROLL_SPEED, |collider| {
+							ROLL_SPEED.min(collider.half_width.min(collider.half_height) - 1.0).max(0.0)
+						});
+						*velocity = if vx == 0 && vy == 0 {
+							Velocity::in_direction(*sto_direction.get(hero_id).unwrap(), roll_speed)
+						} else if vx == 0 || vy == 0 {
+							Velocity {
+								x: f32::try_from(vx).unwrap() * roll_speed,
+								y: f32::try_from(vy).unwrap() * roll_speed,
+							}
 						} else {
-							// Hero is strafing/retreating/standing still -> slash attack.
-							let slash_attack_collider = HalfDiskCollider {
-								radius: SLASH_ATTACK_RADIUS,
-								direction: hero_direction,
-							};
-							let slash_attack_animation = DirectionalAnimation::new(
-								sprite_sheets.slash_attack.clone(),
-								vec!(DirectionalFrame {
-									up: 0,
-									down: 1,
-									left: 2,
-									right: 3,
-									duration: None,
-								}),
-							);
-							let slash_attack_id = entities
-								.build_entity()
-								.with(SlashAttack::new(hero_id), &mut sto_slash_attack)
-								.with(Removal::new(TiedToRegion), &mut sto_removal_tied_to_region)
-								.with(Removal::new(TiedToEntity(hero_id)), &mut sto_removal_tied_to_entity)
-								.with(hero_position, &mut sto_position)
-								.with(hero_direction, &mut sto_direction)
-								.with(slash_attack_collider, &mut sto_disk_arc_collider)
-								.with(slash_attack_animation, &mut sto_directional_animation)
-								.build();
-							hero.state = HeroState::Slashing {
-								slash_attack_id,
-								frames_left: SLASH_FRAMES,
-							};
+							Velocity {
+								x: f32::try_from(vx).unwrap() * FRAC_1_SQRT_2 * roll_speed,
+								y: f32::try_from(vy).unwrap() * FRAC_1_SQRT_2 * roll_speed,
+							}
+						};
+						// Rolling grants invulnerability, unless the hero already has enough of it.
+						let already_invulnerable = sto_invulnerable.get(hero_id)
+							.map_or(false, |invulnerable| invulnerable.frames_left() >= ROLL_FRAMES);
+						if !already_invulnerable {
+							sto_invulnerable.insert(hero_id, Invulnerable::without_blinking(ROLL_FRAMES)).unwrap();
+						}
+						hero.state = HeroState::Rolling { frames_left: ROLL_FRAMES };
+					}
+					// Holding the sword long enough charges a spin attack.
+					if hero.state == HeroState::FreelyMoving && self.primary_action_held_frames >= CHARGE_FRAMES {
+						*velocity = Velocity::default();
+						hero.state = HeroState::Charging;
+						sto_base_tint.insert(hero_id, BaseTint(CHARGED_TINT)).unwrap();
+					}
+					// Check for item selection and use.
+					if let Some(item_slot) = sto_item_slot.get_mut(hero_id) {
+						if !self.next_item_action_down_last_frame && next_item_action_down {
+							item_slot.item = item_slot.item.next();
+						}
+						let still_free = hero.state == HeroState::FreelyMoving;
+						if still_free && !self.secondary_action_down_last_frame && secondary_action_down && item_slot.is_ready() {
+							// Only one boomerang can be in flight at a time.
+							let boomerang_in_flight = item_slot.item == Item::Boomerang
+								&& (&sto_boomerang).join().any(|boomerang| boomerang.source_id() == hero_id);
+							if !boomerang_in_flight {
+								item_slot.start_cooldown(ITEM_COOLDOWN_FRAMES);
+								item_uses.push((hero_id, item_slot.item));
+							}
 						}
 					}
 				},
@@ -223,14 +322,13 @@ impl<'a> System<'a> for HeroControl {
 				HeroState::Thrusting { thrust_attack_id, ref mut frames_left } => {
 					let thrust_attack = sto_thrust_attack.get_mut(thrust_attack_id).unwrap();
 					let hero_direction = sto_direction.get(hero_id).unwrap();
+					let thrust_speed = match sto_combo.get(hero_id).and_then(Combo::current_strike) {
+						Some(Strike { attack: StrikeAttack::Thrust { speed }, .. }) => speed,
+						_ => 0.0,
+					};
 					// Rush forward if the thrust is active.
 					*velocity = if thrust_attack.is_active() {
-						match hero_direction {
-							Direction::Up => Velocity { x: 0.0, y: THRUST_SPEED },
-							Direction::Down => Velocity { x: 0.0, y: -THRUST_SPEED },
-							Direction::Left => Velocity { x: -THRUST_SPEED, y: 0.0 },
-							Direction::Right => Velocity { x: THRUST_SPEED, y: 0.0 },
-						}
+						Velocity::in_direction(*hero_direction, thrust_speed)
 					} else {
 						Velocity { x: 0.0, y: 0.0 }
 					};
@@ -241,9 +339,360 @@ impl<'a> System<'a> for HeroControl {
 						entities.delete(thrust_attack_id).unwrap();
 					}
 				},
+				// In the middle of a dodge roll, keeping its velocity
+				HeroState::Rolling { ref mut frames_left } => {
+					// Reduce frames left and return control if finished rolling.
+					*frames_left -= 1;
+					if *frames_left == 0 {
+						hero.state = HeroState::FreelyMoving;
+						hero.roll_cooldown_frames_left = ROLL_COOLDOWN_FRAMES;
+						*velocity = Velocity::default();
+					}
+				},
+				// Holding a charged spin attack
+				HeroState::Charging => {
+					*velocity = Velocity::default();
+					if !primary_action_down {
+						// Released -> spin attack.
+						sto_base_tint.remove(hero_id);
+						let hero_position = *sto_position.get(hero_id).unwrap();
+						let hero_faction = *sto_faction.get(hero_id).unwrap();
+						let weapon_damage = sto_weapon_damage.get(hero_id).unwrap();
+						let spin_attack_id = SpinAttack::spawn(
+							&entities,
+							hero_id,
+							hero_faction,
+							weapon_damage.spin,
+							hero_position,
+							(
+								&mut sto_spin_attack,
+								&mut sto_removal_tied_to_region,
+								&mut sto_removal_tied_to_entity,
+								&mut sto_position,
+								&mut sto_disk_collider,
+							),
+						);
+						hero.state = HeroState::Spinning {
+							spin_attack_id,
+							frames_left: SPIN_FRAMES,
+						};
+					}
+				},
+				// In the middle of a spin attack
+				HeroState::Spinning { spin_attack_id, ref mut frames_left } => {
+					*velocity = Velocity::default();
+					if *frames_left % SPIN_TURN_PERIOD == 0 {
+						let hero_direction = sto_direction.get_mut(hero_id).unwrap();
+						*hero_direction = hero_direction.clockwise();
+					}
+					// Reduce frames left and return control if finished spinning.
+					*frames_left -= 1;
+					if *frames_left == 0 {
+						hero.state = HeroState::FreelyMoving;
+						entities.delete(spin_attack_id).unwrap();
+					}
+				},
+				// Spinning in place before collapsing
+				HeroState::Dying { ref mut frames_left } => {
+					*velocity = Velocity::default();
+					if *frames_left % HERO_DEATH_SPIN_PERIOD == 0 {
+						let hero_direction = sto_direction.get_mut(hero_id).unwrap();
+						*hero_direction = hero_direction.clockwise();
+					}
+					// Reduce frames left and finish dying.
+					*frames_left -= 1;
+					if *frames_left == 0 {
+						hero.state = HeroState::Dead;
+					}
+				},
+				// No control until respawning
+				HeroState::Dead => *velocity = Velocity::default(),
+			}
+			// Pressing the primary action within the current strike's cancel window chains into the next strike.
+			let current_attack = match hero.state {
+				HeroState::Slashing { slash_attack_id, frames_left } => Some((slash_attack_id, frames_left)),
+				HeroState::Thrusting { thrust_attack_id, frames_left } => Some((thrust_attack_id, frames_left)),
+				_ => None,
+			};
+			if let Some((attack_id, frames_left)) = current_attack {
+				if let Some(strike) = sto_combo.get_mut(hero_id).and_then(|combo| combo.next(frames_left)) {
+					entities.delete(attack_id).unwrap();
+					let hero_faction = *sto_faction.get(hero_id).unwrap();
+					hero.state = start_strike(
+						&entities,
+						&sprite_sheets,
+						hero_id,
+						hero_faction,
+						strike,
+						(
+							&mut sto_slash_attack,
+							&mut sto_thrust_attack,
+							&mut sto_removal_tied_to_region,
+							&mut sto_removal_tied_to_entity,
+							&mut sto_position,
+							&mut sto_direction,
+							&mut sto_disk_arc_collider,
+							&mut sto_rectangle_collider,
+							&mut sto_directional_animation,
+						),
+					);
+				}
+			}
+		}
+
+		// Heroes hold their shields raised while strafing, as long as they're free to move.
+		for shield in (&mut sto_shield).join() {
+			if let Some(hero) = sto_hero.get(shield.bearer_id()) {
+				shield.set_raised(strafe_action_down && hero.state == HeroState::FreelyMoving);
+			}
+		}
+
+		// Use items.
+		for (hero_id, item) in item_uses {
+			let hero_position = *sto_position.get(hero_id).unwrap();
+			let hero_direction = *sto_direction.get(hero_id).unwrap();
+			let hero_faction = *sto_faction.get(hero_id).unwrap();
+			let weapon_damage = *sto_weapon_damage.get(hero_id).unwrap();
+			match item {
+				Item::Bow => {
+					Projectile::spawn(
+						&entities,
+						sprite_sheets.arrow_attack.clone(),
+						ProjectileConfig::arrow(BOW_ARROW_SPEED, weapon_damage.arrow),
+						hero_faction,
+						hero_position,
+						hero_direction,
+						(
+							&mut sto_projectile,
+							&mut sto_removal_tied_to_region,
+							&mut sto_position,
+							&mut sto_velocity,
+							&mut sto_direction,
+							&mut sto_rectangle_collider,
+							&mut sto_directional_animation,
+						),
+					);
+				},
+				Item::Bomb => {
+					entities
+						.build_entity()
+						.with(Bomb::new(hero_faction, weapon_damage.bomb), &mut sto_bomb)
+						.with(Removal::new(TiedToRegion), &mut sto_removal_tied_to_region)
+						.with(hero_position, &mut sto_position)
+						.with(SimpleAnimation::new(
+							sprite_sheets.items.clone(),
+							vec!(SimpleFrame {
+								sprite_number: Item::Bomb.sprite_number(),
+								duration: None,
+							}),
+						), &mut sto_simple_animation)
+						.build();
+				},
+				Item::Boomerang => {
+					let boomerang_velocity = match hero_direction {
+						Direction::Up => Velocity { x: 0.0, y: BOOMERANG_SPEED },
+						Direction::Down => Velocity { x: 0.0, y: -BOOMERANG_SPEED },
+						Direction::Left => Velocity { x: -BOOMERANG_SPEED, y: 0.0 },
+						Direction::Right => Velocity { x: BOOMERANG_SPEED, y: 0.0 },
+					};
+					entities
+						.build_entity()
+						.with(Boomerang::new(hero_id, hero_faction, weapon_damage.boomerang), &mut sto_boomerang)
+						.with(Removal::new(TiedToRegion), &mut sto_removal_tied_to_region)
+						.with(Removal::new(TiedToEntity(hero_id)), &mut sto_removal_tied_to_entity)
+						.with(hero_position, &mut sto_position)
+						.with(boomerang_velocity, &mut sto_velocity)
+						.with(Boomerang::collider(), &mut sto_rectangle_collider)
+						.with(SimpleAnimation::new(
+							sprite_sheets.items.clone(),
+							vec!(SimpleFrame {
+								sprite_number: Item::Boomerang.sprite_number(),
+								duration: None,
+							}),
+						), &mut sto_simple_animation)
+						.build();
+				},
 			}
 		}
 
 		self.primary_action_down_last_frame = primary_action_down;
+		self.secondary_action_down_last_frame = secondary_action_down;
+		self.next_item_action_down_last_frame = next_item_action_down;
+		self.roll_action_down_last_frame = roll_action_down;
+	}
+}
+
+/// The component storages a sword strike is built into.
+type StrikeStorages<'s, 'a> = (
+	&'s mut WriteStorage<'a, SlashAttack>,
+	&'s mut WriteStorage<'a, ThrustAttack>,
+	&'s mut WriteStorage<'a, Removal<TiedToRegion>>,
+	&'s mut WriteStorage<'a, Removal<TiedToEntity>>,
+	&'s mut WriteStorage<'a, Position>,
+	&'s mut WriteStorage<'a, Direction>,
+	&'s mut WriteStorage<'a, HalfDiskCollider>,
+	&'s mut WriteStorage<'a, RectangleCollider>,
+	&'s mut WriteStorage<'a, DirectionalAnimation>,
+);
+
+/// Starts `strike` by the hero `hero_id` and returns the state the hero is in while performing it.
+fn start_strike(
+	entities: &Entities,
+	sprite_sheets: &SpriteSheets,
+	hero_id: Entity,
+	hero_faction: Faction,
+	strike: Strike,
+	(
+		sto_slash_attack,
+		sto_thrust_attack,
+		sto_removal_tied_to_region,
+		sto_removal_tied_to_entity,
+		sto_position,
+		sto_direction,
+		sto_half_disk_collider,
+		sto_rectangle_collider,
+		sto_directional_animation,
+	): StrikeStorages,
+) -> HeroState {
+	let hero_position = *sto_position.get(hero_id).unwrap();
+	let hero_direction = *sto_direction.get(hero_id).unwrap();
+	match strike.attack {
+		StrikeAttack::Slash { radius } => HeroState::Slashing {
+			slash_attack_id: SlashAttack::spawn(
+				entities,
+				sprite_sheets.slash_attack.clone(),
+				hero_id,
+				hero_faction,
+				strike.damage,
+				radius,
+				hero_position,
+				hero_direction,
+				(
+					sto_slash_attack,
+					sto_removal_tied_to_region,
+					sto_removal_tied_to_entity,
+					sto_position,
+					sto_direction,
+					sto_half_disk_collider,
+					sto_directional_animation,
+				),
+			),
+			frames_left: strike.frames,
+		},
+		StrikeAttack::Thrust { .. } => HeroState::Thrusting {
+			thrust_attack_id: ThrustAttack::spawn(
+				entities,
+				sprite_sheets.thrust_attack.clone(),
+				hero_id,
+				hero_faction,
+				strike.damage,
+				hero_position,
+				hero_direction,
+				(
+					sto_thrust_attack,
+					sto_removal_tied_to_region,
+					sto_removal_tied_to_entity,
+					sto_position,
+					sto_direction,
+					sto_rectangle_collider,
+					sto_directional_animation,
+				),
+			),
+			frames_left: strike.frames,
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		component::{Direction, HeroState, Position},
+		headless::{Headless, ARENA},
+		input_bindings::Actions,
+		resource::{InputScript, InputStep},
+	};
+
+	fn assert_moved_by(from: Position, to: Position, dx: f32, dy: f32) {
+		assert!((to.x - from.x - dx).abs() < 1e-3 && (to.y - from.y - dy).abs() < 1e-3, "{:?} -> {:?}", from, to);
+	}
+
+	#[test]
+	fn thrust_then_slash_script() {
+		let mut headless = Headless::with_start(0, ARENA, 0).unwrap();
+		headless.set_script(InputScript::load("assets/scripts/thrust_then_slash.ron").unwrap());
+		let start = headless.hero_position().unwrap();
+		// Walking left.
+		headless.step(10);
+		assert_eq!(headless.hero_state(), Some(HeroState::FreelyMoving));
+		assert_eq!(headless.hero_direction(), Some(Direction::Left));
+		assert_moved_by(start, headless.hero_position().unwrap(), -50.0, 0.0);
+		// Pressing the attack while advancing thrusts.
+		headless.step(1);
+		assert!(matches!(headless.hero_state(), Some(HeroState::Thrusting { .. })));
+		// Holding the attack doesn't start another one after the thrust ends.
+		headless.step(10);
+		assert_eq!(headless.hero_state(), Some(HeroState::FreelyMoving));
+		// Strafing right keeps facing left.
+		let before_strafe = headless.hero_position().unwrap();
+		headless.step(10);
+		assert_eq!(headless.hero_direction(), Some(Direction::Left));
+		assert_moved_by(before_strafe, headless.hero_position().unwrap(), 50.0, 0.0);
+		// Pressing the attack while retreating slashes.
+		headless.step(1);
+		assert!(matches!(headless.hero_state(), Some(HeroState::Slashing { .. })));
+		headless.step(9);
+		assert_eq!(headless.hero_state(), Some(HeroState::FreelyMoving));
+		assert_eq!(headless.hero_direction(), Some(Direction::Left));
+		assert_eq!(headless.hero_position().unwrap().y, start.y);
+	}
+
+	#[test]
+	fn attack_starts_only_on_press() {
+		let mut headless = Headless::with_start(0, ARENA, 0).unwrap();
+		headless.set_script(InputScript::new(vec!(
+			InputStep { frames: 20, actions: vec!(Actions::Primary) },
+			InputStep { frames: 1, actions: vec!() },
+			InputStep { frames: 1, actions: vec!(Actions::Primary) },
+		)));
+		headless.step(1);
+		assert!(matches!(headless.hero_state(), Some(HeroState::Slashing { .. })));
+		headless.step(9);
+		assert_eq!(headless.hero_state(), Some(HeroState::FreelyMoving));
+		// Still held after the slash ends: no new slash.
+		headless.step(10);
+		assert_eq!(headless.hero_state(), Some(HeroState::FreelyMoving));
+		// Released, then pressed again.
+		headless.step(2);
+		assert!(matches!(headless.hero_state(), Some(HeroState::Slashing { .. })));
+	}
+
+	#[test]
+	fn facing_follows_movement_table() {
+		use Actions::{Down, Left, Right, Strafe, Up};
+		use Direction as D;
+		let cases: Vec<(Direction, Vec<Actions>, Direction)> = vec!(
+			// Moving in a cardinal direction always faces that way.
+			(D::Up, vec!(Down), D::Down),
+			(D::Down, vec!(Up), D::Up),
+			(D::Right, vec!(Left), D::Left),
+			(D::Left, vec!(Right), D::Right),
+			// Moving diagonally away from the facing direction turns around.
+			(D::Up, vec!(Down, Right), D::Down),
+			(D::Down, vec!(Up, Left), D::Up),
+			(D::Right, vec!(Down, Left), D::Left),
+			(D::Left, vec!(Up, Right), D::Right),
+			// Moving diagonally partly along the facing direction keeps it.
+			(D::Right, vec!(Up, Right), D::Right),
+			(D::Up, vec!(Up, Left), D::Up),
+			// Strafing never turns.
+			(D::Left, vec!(Right, Strafe), D::Left),
+		);
+		let mut headless = Headless::with_start(0, ARENA, 0).unwrap();
+		for (facing, actions, expected) in cases {
+			*headless.world().write_storage::<Direction>().get_mut(headless.hero_id()).unwrap() = facing;
+			headless.set_script(InputScript::new(vec!(InputStep { frames: 1, actions: actions.clone() })));
+			headless.step(1);
+			assert_eq!(headless.hero_direction(), Some(expected), "facing {:?} and holding {:?}", facing, actions);
+		}
 	}
 }
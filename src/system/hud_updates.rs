@@ -2,6 +2,7 @@ use crate::{
 	component::{
 		Health,
 		Hero,
+		ItemSlot,
 	},
 	resource::{Hud, Textures},
 };
@@ -23,6 +24,7 @@ impl<'a> System<'a> for HudUpdates {
 		ReadExpect<'a, Textures>,
 		ReadStorage<'a, Hero>,
 		ReadStorage<'a, Health>,
+		ReadStorage<'a, ItemSlot>,
 		WriteStorage<'a, UiImage>,
 		WriteStorage<'a, UiTransform>,
 	);
@@ -33,10 +35,22 @@ impl<'a> System<'a> for HudUpdates {
 		textures,
 		sto_hero,
 		sto_health,
+		sto_item_slot,
 		mut sto_ui_image,
 		mut sto_ui_transform,
 	): Self::SystemData) {
-		for (_hero, health) in (&sto_hero, &sto_health).join() {
+		const HEARTS_MARGIN_TOP: f32 = 10.0;
+		const HEARTS_MARGIN_LEFT: f32 = 10.0;
+		const HEARTS_PADDING: f32 = 10.0;
+		const HEARTS_WIDTH: f32 = 13.0;
+		const HEARTS_HEIGHT: f32 = 12.0;
+		const HEARTS_SCALE: f32 = 2.0;
+		const ITEM_SIZE: f32 = 16.0;
+		const ITEM_SCALE: f32 = 2.0;
+		/// The number of item sprites in the items texture.
+		const ITEM_COUNT: f32 = 3.0;
+
+		for (_hero, health, item_slot) in (&sto_hero, &sto_health, sto_item_slot.maybe()).join() {
 			if hud.max_hearts > health.max() {
 				// Delete excess heart images.
 				for to_delete in health.max()..hud.max_hearts {
@@ -46,12 +60,6 @@ impl<'a> System<'a> for HudUpdates {
 			} else if hud.max_hearts < health.max() {
 				// Add heart images.
 				for i in hud.max_hearts..health.max() {
-					const HEARTS_MARGIN_TOP: f32 = 10.0;
-					const HEARTS_MARGIN_LEFT: f32 = 10.0;
-					const HEARTS_PADDING: f32 = 10.0;
-					const HEARTS_WIDTH: f32 = 13.0;
-					const HEARTS_HEIGHT: f32 = 12.0;
-					const HEARTS_SCALE: f32 = 2.0;
 					let heart_image = entities
 						.build_entity()
 						.with(
@@ -95,6 +103,50 @@ impl<'a> System<'a> for HudUpdates {
 					*right = 1.0 / 3.0;
 				}
 			}
+			// Show the selected item just to the right of the heart row.
+			if let Some(item_slot) = item_slot {
+				let item_left = item_slot.item.sprite_number() as f32 / ITEM_COUNT;
+				let item_right = (item_slot.item.sprite_number() + 1) as f32 / ITEM_COUNT;
+				let item_x = HEARTS_MARGIN_LEFT + (hud.max_hearts as f32) * (HEARTS_PADDING + HEARTS_WIDTH * HEARTS_SCALE);
+				match hud.item_image {
+					Some(item_image) => {
+						if let UiImage::PartialTexture { left, right, .. } = sto_ui_image.get_mut(item_image).unwrap() {
+							*left = item_left;
+							*right = item_right;
+						}
+						sto_ui_transform.get_mut(item_image).unwrap().local_x = item_x;
+					},
+					None => {
+						let item_image = entities
+							.build_entity()
+							.with(
+								UiImage::PartialTexture {
+									tex: textures.items.clone(),
+									left: item_left,
+									right: item_right,
+									bottom: 1.0,
+									top: 0.0,
+								},
+								&mut sto_ui_image,
+							)
+							.with(
+								UiTransform::new(
+									"".to_string(),
+									Anchor::TopLeft,
+									Anchor::TopLeft,
+									item_x,
+									-HEARTS_MARGIN_TOP,
+									0.0,
+									ITEM_SIZE * ITEM_SCALE,
+									ITEM_SIZE * ITEM_SCALE,
+								),
+								&mut sto_ui_transform,
+							)
+							.build();
+						hud.item_image = Some(item_image);
+					},
+				}
+			}
 		}
 	}
 }
@@ -3,10 +3,9 @@ use crate::component::{Invulnerable, InvulnerableFinished};
 use amethyst::{
 	derive::SystemDesc,
 	ecs::{Entity, Entities, Join, System, SystemData, WriteStorage},
-	renderer::resources::Tint,
 };
 
-/// Tracks when invulnerability has expired and removes it.
+/// Tracks when invulnerability has expired and removes it. The blinking while invulnerable is drawn by `TintUpdates`.
 #[derive(SystemDesc)]
 pub struct Invulnerability;
 
@@ -14,13 +13,12 @@ impl<'a> System<'a> for Invulnerability {
 	type SystemData = (
 		Entities<'a>,
 		WriteStorage<'a, Invulnerable>,
-		WriteStorage<'a, Tint>,
 	);
 
-	fn run(&mut self, (entities, mut sto_invulnerable, mut sto_tint): Self::SystemData) {
+	fn run(&mut self, (entities, mut sto_invulnerable): Self::SystemData) {
 		let mut entities_finished_invulnerable: Vec<Entity> = Vec::new();
 		for (id, invulnerable) in (&entities, &mut sto_invulnerable).join() {
-			if let InvulnerableFinished::Yes = invulnerable.update(id, &mut sto_tint) {
+			if let InvulnerableFinished::Yes = invulnerable.update() {
 				entities_finished_invulnerable.push(id);
 			}
 		}
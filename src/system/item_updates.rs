@@ -0,0 +1,64 @@
+use crate::{
+	component::{
+		Bomb,
+		Boomerang,
+		Position,
+		Velocity,
+	},
+	constants::*,
+};
+
+use amethyst::{
+	derive::SystemDesc,
+	ecs::{Entities, Join, ReadStorage, System, SystemData, WriteStorage},
+};
+
+/// Burns down bomb fuses and steers returning boomerangs.
+#[derive(SystemDesc)]
+pub struct ItemUpdates;
+
+impl<'a> System<'a> for ItemUpdates {
+	type SystemData = (
+		Entities<'a>,
+		WriteStorage<'a, Bomb>,
+		WriteStorage<'a, Boomerang>,
+		ReadStorage<'a, Position>,
+		WriteStorage<'a, Velocity>,
+	);
+
+	fn run(&mut self, (
+		entities,
+		mut sto_bomb,
+		mut sto_boomerang,
+		sto_position,
+		mut sto_velocity,
+	): Self::SystemData) {
+		// Bomb updates.
+		for bomb in (&mut sto_bomb).join() {
+			bomb.update();
+		}
+		// Boomerang updates.
+		for (boomerang_id, boomerang, boomerang_position, boomerang_velocity) in (
+			&entities,
+			&mut sto_boomerang,
+			&sto_position,
+			&mut sto_velocity,
+		).join() {
+			boomerang.update();
+			if !boomerang.is_returning() {
+				continue;
+			}
+			// Fly back towards the thrower, and disappear on reaching it.
+			let source_position = sto_position.get(boomerang.source_id()).unwrap();
+			let dx = source_position.x - boomerang_position.x;
+			let dy = source_position.y - boomerang_position.y;
+			let distance = f32::sqrt(dx.powi(2) + dy.powi(2));
+			if distance <= BOOMERANG_SPEED {
+				entities.delete(boomerang_id).unwrap();
+			} else {
+				boomerang_velocity.x = dx * BOOMERANG_SPEED / distance;
+				boomerang_velocity.y = dy * BOOMERANG_SPEED / distance;
+			}
+		}
+	}
+}
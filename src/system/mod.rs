@@ -7,9 +7,14 @@ mod enemy_control;
 mod hero_control;
 mod hud_updates;
 mod invulnerability;
+mod item_updates;
 mod knockback;
 mod motion;
+mod player_input;
+mod projectile_updates;
+mod scripted_input;
 mod static_collision_detection;
+mod tint_updates;
 
 pub use animation::Animation;
 pub use attack_updates::AttackUpdates;
@@ -20,6 +25,11 @@ pub use enemy_control::EnemyControl;
 pub use hero_control::HeroControl;
 pub use hud_updates::HudUpdates;
 pub use invulnerability::Invulnerability;
+pub use item_updates::ItemUpdates;
 pub use knockback::Knockback;
 pub use motion::Motion;
+pub use player_input::PlayerInput;
+pub use projectile_updates::ProjectileUpdates;
+pub use scripted_input::ScriptedInput;
 pub use static_collision_detection::StaticCollisionDetection;
+pub use tint_updates::TintUpdates;
@@ -0,0 +1,26 @@
+use crate::{
+	input_bindings::InputBindings,
+	resource::ActionState,
+};
+
+use amethyst::{
+	derive::SystemDesc,
+	ecs::{System, SystemData, Write},
+	input::InputHandler,
+	shred::Read,
+};
+
+/// Copies the player's held actions from the input handler into the action state.
+#[derive(SystemDesc)]
+pub struct PlayerInput;
+
+impl<'a> System<'a> for PlayerInput {
+	type SystemData = (
+		Read<'a, InputHandler<InputBindings>>,
+		Write<'a, ActionState>,
+	);
+
+	fn run(&mut self, (input, mut action_state): Self::SystemData) {
+		*action_state = ActionState::from_input(&input);
+	}
+}
@@ -0,0 +1,50 @@
+use crate::component::{
+	collider::RectangleCollider,
+	Direction,
+	Projectile,
+	ProjectileStopped,
+	Velocity,
+};
+
+use amethyst::{
+	derive::SystemDesc,
+	ecs::{Entities, Join, System, SystemData, WriteStorage},
+};
+
+/// Ages projectiles, bends arcing ones, and turns each to face the way it's flying.
+#[derive(SystemDesc)]
+pub struct ProjectileUpdates;
+
+impl<'a> System<'a> for ProjectileUpdates {
+	type SystemData = (
+		Entities<'a>,
+		WriteStorage<'a, Projectile>,
+		WriteStorage<'a, Velocity>,
+		WriteStorage<'a, Direction>,
+		WriteStorage<'a, RectangleCollider>,
+	);
+
+	fn run(&mut self, (
+		entities,
+		mut sto_projectile,
+		mut sto_velocity,
+		mut sto_direction,
+		mut sto_rectangle_collider,
+	): Self::SystemData) {
+		for (projectile_id, projectile, velocity, direction, collider) in (
+			&entities,
+			&mut sto_projectile,
+			&mut sto_velocity,
+			&mut sto_direction,
+			&mut sto_rectangle_collider,
+		).join() {
+			if let ProjectileStopped::Yes = projectile.update(velocity) {
+				entities.delete(projectile_id).unwrap();
+				continue;
+			}
+			// Keep the sprite and collider lined up with the flight, which may have turned or bounced.
+			*direction = Direction::nearest(velocity.x, velocity.y);
+			*collider = Projectile::compute_collider(direction);
+		}
+	}
+}
@@ -0,0 +1,21 @@
+use crate::resource::{ActionState, InputScript};
+
+use amethyst::{
+	derive::SystemDesc,
+	ecs::{System, SystemData, Write},
+};
+
+/// Plays back the current input script into the action state, one frame per run.
+#[derive(SystemDesc)]
+pub struct ScriptedInput;
+
+impl<'a> System<'a> for ScriptedInput {
+	type SystemData = (
+		Write<'a, InputScript>,
+		Write<'a, ActionState>,
+	);
+
+	fn run(&mut self, (mut input_script, mut action_state): Self::SystemData) {
+		*action_state = input_script.next_frame();
+	}
+}
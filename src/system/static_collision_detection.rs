@@ -1,11 +1,14 @@
 use crate::{
 	component::{
-		ArrowAttack,
+		Boomerang,
 		collider::RectangleCollider,
 		Direction,
 		Faction,
 		Position,
+		Projectile,
+		ProjectileStopped,
 		Terrain,
+		Velocity,
 	},
 	constants::*,
 	resource::CurrentRegion,
@@ -28,7 +31,9 @@ impl<'a> System<'a> for StaticCollisionDetection {
 		ReadStorage<'a, RectangleCollider>,
 		ReadStorage<'a, Terrain>,
 		WriteStorage<'a, Position>,
-		ReadStorage<'a, ArrowAttack>,
+		WriteStorage<'a, Projectile>,
+		WriteStorage<'a, Velocity>,
+		WriteStorage<'a, Boomerang>,
 	);
 
 	fn run(&mut self, (
@@ -38,7 +43,9 @@ impl<'a> System<'a> for StaticCollisionDetection {
 		sto_rectangle_collider,
 		sto_terrain,
 		mut sto_position,
-		sto_arrow_attack,
+		mut sto_projectile,
+		mut sto_velocity,
+		mut sto_boomerang,
 	): Self::SystemData) {
 		// Determines if the tile containing (x, y) is a wall.
 		let is_wall = |x, y| {
@@ -57,6 +64,19 @@ impl<'a> System<'a> for StaticCollisionDetection {
 			};
 			(low, high)
 		};
+		// Determines if any part of the given collider and position is inside a wall.
+		let touches_wall = |collider: &RectangleCollider, position: &Position| {
+			let (low, high) = get_low_high(collider, position);
+			let x_steps = ((high.x - low.x) / TILE_SIZE).ceil() as i32 + 1;
+			let y_steps = ((high.y - low.y) / TILE_SIZE).ceil() as i32 + 1;
+			(0..x_steps).any(|x_step| {
+				let x = low.x + (x_step as f32 * TILE_SIZE).min(high.x - low.x);
+				(0..y_steps).any(|y_step| {
+					let y = low.y + (y_step as f32 * TILE_SIZE).min(high.y - low.y);
+					is_wall(x, y)
+				})
+			})
+		};
 		// Push characters out of obstacles.
 		for (_faction, collider, position) in (
 			&sto_faction,
@@ -192,25 +212,42 @@ impl<'a> System<'a> for StaticCollisionDetection {
 				}
 			}
 		}
-		// Destroy arrows that hit obstacles.
-		'arrow_destruction: for (arrow_attack_id, _arrow_attack, collider, position) in (
+		// Bounce projectiles off obstacles, or destroy them once they run out of bounces.
+		for (projectile_id, projectile, collider, position, velocity) in (
 			&entities,
-			&sto_arrow_attack,
+			&mut sto_projectile,
+			&sto_rectangle_collider,
+			&mut sto_position,
+			&mut sto_velocity,
+		).join() {
+			if !touches_wall(collider, position) {
+				continue;
+			}
+			if let ProjectileStopped::Yes = projectile.bounce() {
+				entities.delete(projectile_id).unwrap();
+				continue;
+			}
+			// Back up to where the projectile was last frame, then reflect its velocity off whichever walls it
+			// ran into, checking each axis of motion separately. If neither axis alone hits a wall, it hit a corner.
+			position.x -= velocity.x;
+			position.y -= velocity.y;
+			let hit_x = touches_wall(collider, &Position { x: position.x + velocity.x, y: position.y });
+			let hit_y = touches_wall(collider, &Position { x: position.x, y: position.y + velocity.y });
+			if hit_x || !hit_y {
+				velocity.x = -velocity.x;
+			}
+			if hit_y || !hit_x {
+				velocity.y = -velocity.y;
+			}
+		}
+		// Turn back boomerangs that hit obstacles.
+		for (boomerang, collider, position) in (
+			&mut sto_boomerang,
 			&sto_rectangle_collider,
 			&sto_position,
 		).join() {
-			let (low, high) = get_low_high(&collider, &position);
-			let x_steps = ((high.x - low.x) / TILE_SIZE).ceil() as i32 + 1;
-			let y_steps = ((high.y - low.y) / TILE_SIZE).ceil() as i32 + 1;
-			for x_step in 0..x_steps {
-				let x = low.x + (x_step as f32 * TILE_SIZE).min(high.x - low.x);
-				for y_step in 0..y_steps {
-					let y = low.y + (y_step as f32 * TILE_SIZE).min(high.y - low.y);
-					if is_wall(x, y) {
-						entities.delete(arrow_attack_id).unwrap();
-						continue 'arrow_destruction;
-					}
-				}
+			if !boomerang.is_returning() && touches_wall(collider, position) {
+				boomerang.start_returning();
 			}
 		}
 	}
@@ -0,0 +1,43 @@
+use crate::{
+	component::{BaseTint, Invulnerable},
+	constants::*,
+};
+
+use amethyst::{
+	derive::SystemDesc,
+	ecs::{Entities, Join, ReadStorage, System, SystemData, WriteStorage},
+	renderer::{palette::Srgba, resources::Tint, SpriteRender},
+};
+
+/// Sets the tint of each sprite from its base tint, dimmed while it's blinking from invulnerability. This is the only
+/// system that writes `Tint`.
+#[derive(SystemDesc)]
+pub struct TintUpdates;
+
+impl<'a> System<'a> for TintUpdates {
+	type SystemData = (
+		Entities<'a>,
+		ReadStorage<'a, SpriteRender>,
+		ReadStorage<'a, BaseTint>,
+		ReadStorage<'a, Invulnerable>,
+		WriteStorage<'a, Tint>,
+	);
+
+	fn run(&mut self, (entities, sto_sprite_render, sto_base_tint, sto_invulnerable, mut sto_tint): Self::SystemData) {
+		for (id, _sprite_render, base_tint, invulnerable) in (
+			&entities,
+			&sto_sprite_render,
+			sto_base_tint.maybe(),
+			sto_invulnerable.maybe(),
+		).join() {
+			let dimmed = invulnerable.map_or(false, |invulnerable| invulnerable.is_blinking() && invulnerable.is_dimmed());
+			if base_tint.is_none() && !dimmed {
+				sto_tint.remove(id);
+				continue;
+			}
+			let [r, g, b] = base_tint.map_or([1.0, 1.0, 1.0], |base_tint| base_tint.0);
+			let lum = if dimmed { INVULNERABLE_BLINK_LUMINANCE } else { 1.0 };
+			sto_tint.insert(id, Tint(Srgba::new(r * lum, g * lum, b * lum, 1.0))).unwrap();
+		}
+	}
+}